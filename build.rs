@@ -0,0 +1,118 @@
+//! Embeds the built-in themes of `themes/`, keyed by file name. Every file
+//! must parse, prepare with its `extends` chain resolved and be named after
+//! its file, a `light` flag that contradicts the background fails the
+//! build. The theme lists are generated into `$OUT_DIR/collection.rs`.
+
+#[allow(dead_code)]
+#[path = "src/color.rs"]
+mod color;
+#[allow(dead_code)]
+#[path = "src/error.rs"]
+mod error;
+#[allow(dead_code)]
+#[path = "src/extends.rs"]
+mod extends;
+#[allow(dead_code)]
+#[path = "src/models.rs"]
+mod models;
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+struct Entry {
+    key: String,
+    path: PathBuf,
+    light: bool,
+}
+
+/// Parses and prepares one theme file, returns whether it is light.
+fn check(key: &str, dir: &Path) -> Result<bool, String> {
+    let source = |name: &str| {
+        let path = dir.join(name).with_extension("toml");
+        Ok(fs::read_to_string(&path).ok().map(|text| extends::Source {
+            text,
+            file: Some(path),
+        }))
+    };
+    let table = extends::resolve(key, &source).map_err(|e| e.to_string())?;
+    let theme = toml::Value::Table(table)
+        .try_into::<models::Theme>()
+        .map_err(|e| e.to_string())?;
+    if theme.name.as_deref() != Some(key) {
+        return Err(format!("name must be \"{key}\""));
+    }
+    if theme.variant.is_some() && theme.family.is_none() {
+        return Err("variant needs a family".into());
+    }
+    let declared = theme.light;
+    let theme = theme.prepare().map_err(|e| e.to_string())?;
+    let light = theme.has_light_background();
+    match declared {
+        Some(l) if l != light => Err(format!(
+            "light = {l}, but the background is {}",
+            if light { "light" } else { "dark" }
+        )),
+        _ => Ok(light),
+    }
+}
+
+fn list(name: &str, doc: &str, keys: &[&str]) -> String {
+    let mut out = format!("/// {doc}\npub const {name}: [&str; {}] = [\n", keys.len());
+    for key in keys {
+        writeln!(out, "    {key:?},").unwrap();
+    }
+    out.push_str("];\n\n");
+    out
+}
+
+fn main() {
+    let dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("themes");
+    println!("cargo::rerun-if-changed={}", dir.display());
+
+    let mut entries = Vec::new();
+    let mut failed = false;
+    for entry in fs::read_dir(&dir).expect("themes directory").flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != "toml") {
+            continue;
+        }
+        let key = path.file_stem().unwrap().to_string_lossy().into_owned();
+        match check(&key, &dir) {
+            Ok(light) => entries.push(Entry { key, path, light }),
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let keys = |f: fn(&Entry) -> bool| {
+        entries
+            .iter()
+            .filter(|e| f(e))
+            .map(|e| e.key.as_str())
+            .collect::<Vec<_>>()
+    };
+    let mut out = String::new();
+    out += &list("LIST", "Every built-in theme, sorted.", &keys(|_| true));
+    out += &list("DARK_LIST", "Built-in dark themes.", &keys(|e| !e.light));
+    out += &list("LIGHT_LIST", "Built-in light themes.", &keys(|e| e.light));
+    writeln!(out, "/// Theme files in `LIST` order.").unwrap();
+    writeln!(out, "const SOURCES: [&str; {}] = [", entries.len()).unwrap();
+    for e in &entries {
+        writeln!(out, "    include_str!({:?}),", e.path.display().to_string()).unwrap();
+    }
+    out.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("collection.rs"), out).unwrap();
+}
@@ -0,0 +1,356 @@
+use crate::{
+    error::{Error, Result},
+    files::Writer,
+    models::{
+        PreparedTheme,
+        alacritty::{Config, Font, FontDescription},
+    },
+    templ,
+};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_NVIM_CONFIG_PATH: &str = "nvim/init.lua";
+const DEFAULT_ALACRITTY_CONFIG_PATH: &str = "alacritty/alacritty.toml";
+const DEFAULT_LUALINE_THEME_PATH: &str = "nvim/lua/lualine/themes/tvibe.lua";
+
+/// Names of the targets [`apply`] knows.
+pub const TARGETS: [&str; 3] = ["nvim", "lualine", "alacritty"];
+
+const NVIM_START_MARK: &str = "-- ====THEMESYNCSTARTBLOCK====";
+const NVIM_END_MARK: &str = "-- ====THEMESYNCENDBLOCK====";
+
+/// Config files written by [`apply`].
+#[derive(Debug, Clone)]
+pub struct Targets {
+    pub nvim_config: PathBuf,
+    pub lualine_theme: PathBuf,
+    pub alacritty_config: PathBuf,
+    /// Targets written by [`apply`], `None` for every one of [`TARGETS`].
+    pub enabled: Option<Vec<String>>,
+}
+
+impl Targets {
+    /// The default locations below `home`.
+    pub fn in_home(home: &Path) -> Self {
+        Self::in_config_dir(&home.join(".config"))
+    }
+
+    /// The default locations below a config directory such as
+    /// `$XDG_CONFIG_HOME`.
+    pub fn in_config_dir(dir: &Path) -> Self {
+        Self {
+            nvim_config: dir.join(DEFAULT_NVIM_CONFIG_PATH),
+            lualine_theme: dir.join(DEFAULT_LUALINE_THEME_PATH),
+            alacritty_config: dir.join(DEFAULT_ALACRITTY_CONFIG_PATH),
+            enabled: None,
+        }
+    }
+
+    pub fn is_enabled(&self, target: &str) -> bool {
+        self.enabled
+            .as_ref()
+            .is_none_or(|e| e.iter().any(|n| n == target))
+    }
+}
+
+/// Puts `content` between the tvibe marks of `text`, the block is appended
+/// when the marks are missing. Only the first block is replaced, a start
+/// mark without an end mark gets one right after `content`.
+fn splice_block<'a>(text: &'a str, content: &'a str) -> String {
+    let mut out = Vec::new();
+    let mut lines = text.lines();
+    let mut inserted = false;
+
+    for line in lines.by_ref() {
+        out.push(line);
+        if line == NVIM_START_MARK {
+            out.push(content);
+            inserted = true;
+            break;
+        }
+    }
+    if inserted {
+        let mut replaced = Vec::new();
+        let mut found_end = false;
+        for line in lines.by_ref() {
+            replaced.push(line);
+            if line == NVIM_END_MARK {
+                found_end = true;
+                break;
+            }
+        }
+        out.push(NVIM_END_MARK);
+        // without an end mark the block closes here and nothing is dropped
+        if !found_end {
+            out.extend(replaced);
+        }
+        out.extend(lines);
+    } else {
+        out.extend(["", NVIM_START_MARK, content, NVIM_END_MARK]);
+    }
+
+    out.push("");
+    out.join("\n")
+}
+
+/// Replaces the block between the tvibe marks of the nvim config with the
+/// rendered colorscheme.
+pub fn nvim(theme: &PreparedTheme, path: &Path, writer: &Writer) -> Result<()> {
+    let text = writer.read_to_string(path)?;
+    writer.write(path, &splice_block(&text, &templ::nvim(theme)))
+}
+
+pub fn lualine(theme: &PreparedTheme, path: &Path, writer: &Writer) -> Result<()> {
+    writer.write(path, &templ::lualine(theme))
+}
+
+pub fn load_alacritty_config(path: &Path, writer: &Writer) -> Result<Config> {
+    let buff = writer.read_to_string(path)?;
+    buff.parse::<Config>()
+        .map_err(|e| Error::config(e).in_file(path))
+}
+
+pub fn save_alacritty_config(config: &Config, path: &Path, writer: &Writer) -> Result<()> {
+    writer.write(path, &config.to_string())
+}
+
+/// Replaces the alacritty colors and sets the window opacity of a
+/// transparent style.
+pub fn alacritty(theme: &PreparedTheme, path: &Path, writer: &Writer) -> Result<()> {
+    let mut config = load_alacritty_config(path, writer)?;
+    config
+        .replace_colors_from_theme(theme.palette())
+        .map_err(|e| e.in_file(path))?;
+    if let Some(opacity) = theme.style().window_opacity() {
+        config.set_window_opacity(opacity);
+    }
+    save_alacritty_config(&config, path, writer)
+}
+
+/// Outcome of one target, keyed by its name (`nvim`, `lualine`, ...).
+pub type TargetResult = (String, Result<()>);
+
+/// Applies a prepared theme to every enabled target whose config exists
+/// (lualine follows the nvim config). Returns the result per target name.
+pub fn apply(theme: &PreparedTheme, targets: &Targets, writer: &Writer) -> Vec<TargetResult> {
+    let mut results = Vec::new();
+    if targets.nvim_config.exists() {
+        if targets.is_enabled("nvim") {
+            results.push(("nvim".into(), nvim(theme, &targets.nvim_config, writer)));
+        }
+        if targets.is_enabled("lualine") {
+            results.push((
+                "lualine".into(),
+                lualine(theme, &targets.lualine_theme, writer),
+            ));
+        }
+    }
+    if targets.is_enabled("alacritty") && targets.alacritty_config.exists() {
+        results.push((
+            "alacritty".into(),
+            alacritty(theme, &targets.alacritty_config, writer),
+        ));
+    }
+    results
+}
+
+/// An installed font family and the styles it has faces for.
+#[derive(Debug, Clone)]
+pub struct FontFamily {
+    pub name: String,
+    pub styles: Vec<String>,
+}
+
+impl FontFamily {
+    /// Installed style that is one of `styles`, compared without case and
+    /// spaces.
+    fn find_style(&self, styles: &[&str]) -> Option<&String> {
+        self.styles.iter().find(|s| {
+            let style = s.to_lowercase().replace(' ', "");
+            styles.contains(&style.as_str())
+        })
+    }
+}
+
+/// Updates the alacritty `[font]` section: the `family` (bold/italic
+/// variants follow when the family has such styles installed), the normal
+/// style and the size. Other font settings are kept. Returns warnings, such
+/// as a style that is not installed.
+pub fn set_alacritty_font(
+    path: &Path,
+    family: Option<&FontFamily>,
+    size: Option<f32>,
+    style: Option<&str>,
+    writer: &Writer,
+) -> Result<Vec<String>> {
+    let mut config = load_alacritty_config(path, writer)?;
+    let current = config.font().map_err(|e| e.in_file(path))?;
+    let mut font = Font {
+        size,
+        ..Default::default()
+    };
+
+    let Some(family) = family else {
+        font.normal = style.map(|s| FontDescription {
+            family: None,
+            style: Some(s.into()),
+        });
+        config.set_font(&font).map_err(|e| e.in_file(path))?;
+        return save_alacritty_config(&config, path, writer).map(|()| Vec::new());
+    };
+
+    let mut warnings = Vec::new();
+    if let Some(s) = style
+        && family
+            .find_style(&[&s.to_lowercase().replace(' ', "")])
+            .is_none()
+    {
+        warnings.push(format!("{} has no '{s}' style installed", family.name));
+    }
+    font.normal = Some(FontDescription {
+        family: Some(family.name.clone()),
+        style: style.map(String::from),
+    });
+
+    let variants = [
+        (&mut font.bold, &current.bold, &["bold"][..]),
+        (
+            &mut font.italic,
+            &current.italic,
+            &["italic", "oblique"][..],
+        ),
+        (
+            &mut font.bold_italic,
+            &current.bold_italic,
+            &["bolditalic", "boldoblique"][..],
+        ),
+    ];
+    for (variant, current, styles) in variants {
+        *variant = match family.find_style(styles) {
+            Some(s) => Some(FontDescription {
+                family: Some(family.name.clone()),
+                style: Some(s.clone()),
+            }),
+            // keep an explicit family pointing at the current font
+            None => current
+                .as_ref()
+                .and_then(|d| d.family.as_ref())
+                .map(|_| FontDescription {
+                    family: Some(family.name.clone()),
+                    style: None,
+                }),
+        };
+    }
+
+    config.set_font(&font).map_err(|e| e.in_file(path))?;
+    save_alacritty_config(&config, path, writer)?;
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn family(styles: &[&str]) -> FontFamily {
+        FontFamily {
+            name: "Hack Nerd Font".into(),
+            styles: styles.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn desc(d: &Option<FontDescription>) -> Option<(Option<&str>, Option<&str>)> {
+        d.as_ref()
+            .map(|d| (d.family.as_deref(), d.style.as_deref()))
+    }
+
+    /// Writes `config`, sets the font and returns the new `[font]` section.
+    fn set_font(config: &str, family: &FontFamily, style: Option<&str>) -> (Font, Vec<String>) {
+        let dir = TempDir::new("alacritty-font");
+        let path = dir.join("alacritty.toml");
+        std::fs::write(&path, config).unwrap();
+        let writer = Writer::new(false, false);
+        let warnings = set_alacritty_font(&path, Some(family), Some(12.0), style, &writer).unwrap();
+        let font = load_alacritty_config(&path, &writer)
+            .unwrap()
+            .font()
+            .unwrap();
+        (font, warnings)
+    }
+
+    #[test]
+    fn font_variants_follow_the_installed_styles() {
+        let hack = family(&["Regular", "Bold", "Italic", "Bold Italic"]);
+        let (font, warnings) = set_font("[font.bold]\nfamily = \"Old\"\n", &hack, None);
+        assert!(warnings.is_empty());
+        let name = Some("Hack Nerd Font");
+        assert_eq!(desc(&font.normal), Some((name, None)));
+        assert_eq!(desc(&font.bold), Some((name, Some("Bold"))));
+        assert_eq!(desc(&font.italic), Some((name, Some("Italic"))));
+        assert_eq!(desc(&font.bold_italic), Some((name, Some("Bold Italic"))));
+        assert_eq!(font.size, Some(12.0));
+
+        let oblique = family(&["Book", "Bold Oblique"]);
+        let (font, _) = set_font("", &oblique, Some("Book"));
+        assert_eq!(desc(&font.normal), Some((name, Some("Book"))));
+        assert_eq!(desc(&font.bold), None);
+        assert_eq!(desc(&font.bold_italic), Some((name, Some("Bold Oblique"))));
+    }
+
+    #[test]
+    fn missing_variants_keep_only_an_explicit_family() {
+        let hack = family(&["Regular", "Bold"]);
+        let config = "[font.italic]\nfamily = \"Old\"\nstyle = \"Italic\"\n\n[font.bold_italic]\nstyle = \"Bold Italic\"\n";
+        let (font, warnings) = set_font(config, &hack, Some("Light"));
+        assert_eq!(warnings, ["Hack Nerd Font has no 'Light' style installed"]);
+        let name = Some("Hack Nerd Font");
+        assert_eq!(desc(&font.normal), Some((name, Some("Light"))));
+        assert_eq!(desc(&font.bold), Some((name, Some("Bold"))));
+        // the family follows, the rest of the section is merged as before
+        assert_eq!(desc(&font.italic), Some((name, Some("Italic"))));
+        // no family of its own, alacritty falls back to the normal one
+        assert_eq!(desc(&font.bold_italic), Some((None, Some("Bold Italic"))));
+    }
+
+    const CONFIG: &str = "vim.o.number = true\n";
+
+    fn block(content: &str) -> String {
+        format!("{NVIM_START_MARK}\n{content}\n{NVIM_END_MARK}\n")
+    }
+
+    #[test]
+    fn splice_block_appends_and_then_replaces() {
+        let once = splice_block(CONFIG, "colors 1");
+        assert_eq!(once, format!("{CONFIG}\n{}", block("colors 1")));
+        let twice = splice_block(&once, "colors 2");
+        assert_eq!(twice, format!("{CONFIG}\n{}", block("colors 2")));
+    }
+
+    #[test]
+    fn splice_block_closes_a_block_without_end_mark() {
+        let text = format!("{CONFIG}{NVIM_START_MARK}\nold\nvim.o.wrap = false\n");
+        let once = splice_block(&text, "colors");
+        assert_eq!(
+            once,
+            format!("{CONFIG}{}old\nvim.o.wrap = false\n", block("colors"))
+        );
+        assert_eq!(splice_block(&once, "colors"), once);
+    }
+
+    #[test]
+    fn splice_block_replaces_only_the_first_block() {
+        let text = format!("{}{CONFIG}{}", block("old 1"), block("old 2"));
+        let once = splice_block(&text, "new");
+        assert_eq!(once, format!("{}{CONFIG}{}", block("new"), block("old 2")));
+        assert_eq!(splice_block(&once, "new"), once);
+    }
+
+    #[test]
+    fn splice_block_ignores_a_lone_end_mark() {
+        let text = format!("{NVIM_END_MARK}\n{CONFIG}");
+        assert_eq!(
+            splice_block(&text, "colors"),
+            format!("{text}\n{}", block("colors"))
+        );
+    }
+}
@@ -1,26 +1,4 @@
-use std::io::Write;
-
-pub fn print_palette(colors: &[Color]) {
-    print!("\x1b[48;2;90;90;90m");
-    for _ in colors {
-        print!("    ");
-    }
-    println!("\x1b[0m");
-    for _ in 0..2 {
-        for c in colors {
-            let (r, g, b, _) = c.to_rgba();
-            print!("\x1b[48;2;{};{};{}m    \x1b[0m", r, g, b);
-        }
-        println!();
-    }
-    print!("\x1b[48;2;90;90;90m");
-    for _ in colors {
-        print!("    ");
-    }
-    println!("\x1b[0m");
-
-    std::io::stdout().flush().unwrap();
-}
+use crate::error::{Error, Result};
 
 fn clamp(v: f32, min: f32, max: f32) -> f32 {
     v.max(min).min(max)
@@ -48,11 +26,12 @@ impl Color {
         }
     }
 
-    pub fn from_hex_str(s: &str) -> Result<Self, String> {
+    /// Parses `#rrggbb` or `#rrggbbaa`, errors carry the value but no slot.
+    pub fn from_hex_str(s: &str) -> Result<Self> {
         let hex = s.trim_start_matches('#');
 
         let value = u32::from_str_radix(hex, 16)
-            .map_err(|_| "Invalid hex color".to_string())?;
+            .map_err(|_| Error::theme("", Some(s), "invalid hex color"))?;
 
         match hex.len() {
             6 => {
@@ -68,7 +47,11 @@ impl Color {
                 let a = (value & 0xff) as f32 / 255.0;
                 Ok(Color::from_rgba(r, g, b, a))
             }
-            _ => Err("Hex string must be 6 or 8 characters".to_string()),
+            _ => Err(Error::theme(
+                "",
+                Some(s),
+                "hex color must be 6 or 8 characters",
+            )),
         }
     }
 
@@ -96,7 +79,7 @@ impl Color {
 
         let f = |n: f32| {
             let k = (n + h / 60.0).rem_euclid(6.0);
-            v - v * s * (k.min(4.0 - k).min(1.0)).max(0.0)
+            v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
         };
 
         Self::new(f(5.0), f(3.0), f(1.0), a)
@@ -117,7 +100,7 @@ impl Color {
     //     Self::new(f(0.0), f(8.0), f(4.0), a)
     // }
 
-    pub fn to_rgba(&self) -> (u8, u8, u8, f32) {
+    pub fn to_rgba(self) -> (u8, u8, u8, f32) {
         (
             round_u8(self.red * 255.0),
             round_u8(self.green * 255.0),
@@ -126,7 +109,7 @@ impl Color {
         )
     }
 
-    pub fn to_hex(&self, with_alpha: bool) -> u32 {
+    pub fn to_hex(self, with_alpha: bool) -> u32 {
         let r = round_u8(self.red * 255.0) as u32;
         let g = round_u8(self.green * 255.0) as u32;
         let b = round_u8(self.blue * 255.0) as u32;
@@ -140,7 +123,7 @@ impl Color {
         }
     }
 
-    pub fn to_css(&self, with_alpha: bool) -> String {
+    pub fn to_css(self, with_alpha: bool) -> String {
         if with_alpha {
             format!("#{:08x}", self.to_hex(true))
         } else {
@@ -148,7 +131,19 @@ impl Color {
         }
     }
 
-    pub fn to_hsv(&self) -> (f32, f32, f32) {
+    /// 24-bit SGR foreground escape.
+    pub fn ansi_fg(self) -> String {
+        let (r, g, b, _) = self.to_rgba();
+        format!("\x1b[38;2;{r};{g};{b}m")
+    }
+
+    /// 24-bit SGR background escape.
+    pub fn ansi_bg(self) -> String {
+        let (r, g, b, _) = self.to_rgba();
+        format!("\x1b[48;2;{r};{g};{b}m")
+    }
+
+    pub fn to_hsv(self) -> (f32, f32, f32) {
         let max = self.red.max(self.green).max(self.blue);
         let min = self.red.min(self.green).min(self.blue);
         let delta = max - min;
@@ -213,6 +208,36 @@ impl Color {
         Color::from_hsv(h, s, clamp(val + v, 0.0, 100.0), self.alpha)
     }
 
+    /// CIE L*a*b* under D65.
+    pub fn to_lab(self) -> (f32, f32, f32) {
+        let lin = |c: f32| {
+            if c > 0.04045 {
+                ((c + 0.055) / 1.055).powf(2.4)
+            } else {
+                c / 12.92
+            }
+        };
+        let (r, g, b) = (lin(self.red), lin(self.green), lin(self.blue));
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+        let f = |t: f32| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// CIEDE2000 color difference, around 2 is barely noticeable.
+    pub fn delta_e(self, other: Color) -> f32 {
+        ciede2000(self.to_lab(), other.to_lab())
+    }
+
     // pub fn lighten(&self, v: f32) -> Color {
     //     let (h, s, l) = self.to_hsl();
     //     Color::from_hsl(h, s, clamp(l + v, 0.0, 100.0), self.alpha)
@@ -241,8 +266,133 @@ impl Color {
     // }
 }
 
+/// CIEDE2000 difference of two L*a*b* colors.
+fn ciede2000((l1, a1, b1): (f32, f32, f32), (l2, a2, b2): (f32, f32, f32)) -> f32 {
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let c7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c7 / (c7 + 25f32.powi(7))).sqrt());
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |b: f32, a: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 <= h1 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1 - 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let sl = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_bar;
+    let sh = 1.0 + 0.015 * c_bar * t;
+    let c7 = c_bar.powi(7);
+    let rt = -2.0
+        * (c7 / (c7 + 25f32.powi(7))).sqrt()
+        * (60.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp())
+            .to_radians()
+            .sin();
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt()
+}
+
 impl std::fmt::Display for Color {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_css(false))
     }
 }
+
+/// Serializes as `#rrggbb`, like `Display`.
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Color {
+        Color::from_hex_str(s).unwrap()
+    }
+
+    fn assert_close(a: f32, b: f32, eps: f32) {
+        assert!((a - b).abs() < eps, "{a} != {b}");
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!(hex("#1d2021").to_string(), "#1d2021");
+        assert_eq!(hex("fb4934").to_css(true), "#fb4934ff");
+        assert!(Color::from_hex_str("#12345").is_err());
+        assert!(Color::from_hex_str("#gggggg").is_err());
+    }
+
+    #[test]
+    fn lab_of_srgb_primaries() {
+        let (l, a, b) = hex("#ffffff").to_lab();
+        assert_close(l, 100.0, 0.05);
+        assert_close(a, 0.0, 0.05);
+        assert_close(b, 0.0, 0.05);
+        let (l, a, b) = hex("#ff0000").to_lab();
+        assert_close(l, 53.24, 0.05);
+        assert_close(a, 80.09, 0.05);
+        assert_close(b, 67.20, 0.05);
+    }
+
+    // pairs 1, 7, 17 and 25 of Sharma, Wu and Dalal, "The CIEDE2000
+    // color-difference formula: implementation notes"
+    #[test]
+    fn ciede2000_reference_pairs() {
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+        ];
+        for (a, b, expected) in pairs {
+            assert_close(ciede2000(a, b), expected, 1e-3);
+            assert_close(ciede2000(b, a), expected, 1e-3);
+        }
+    }
+
+    #[test]
+    fn delta_e_of_colors() {
+        assert_eq!(hex("#282828").delta_e(hex("#282828")), 0.0);
+        assert_close(hex("#ffffff").delta_e(hex("#000000")), 100.0, 0.01);
+        assert!(hex("#1d2021").delta_e(hex("#1e2122")) < 1.0);
+    }
+}
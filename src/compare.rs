@@ -0,0 +1,82 @@
+use tvibe::{color::Color, models::PreparedTheme};
+
+const RESET: &str = "\x1b[0m";
+const SLOT_WIDTH: usize = 14;
+
+/// Largest pairwise CIEDE2000 difference.
+fn max_delta_e(colors: &[Color]) -> f32 {
+    let mut max = 0f32;
+    for (i, a) in colors.iter().enumerate() {
+        for b in &colors[i + 1..] {
+            max = max.max(a.delta_e(*b));
+        }
+    }
+    max
+}
+
+/// Prints every palette slot of the prepared themes in aligned columns,
+/// slots differing by more than `threshold` ΔE are highlighted.
+pub fn print(themes: &[PreparedTheme], threshold: f32) {
+    let names = themes.iter().map(|t| t.name()).collect::<Vec<_>>();
+    let col = names.iter().map(|n| n.len()).max().unwrap_or(0).max(12) + 2;
+
+    print!("\x1b[1m{:<SLOT_WIDTH$}", "slot");
+    for name in &names {
+        print!("{name:<col$}");
+    }
+    println!("ΔE{RESET}");
+
+    // every prepared palette has the same slots in the same order
+    let slots = themes
+        .iter()
+        .map(|t| t.palette().slots())
+        .collect::<Vec<_>>();
+    let Some(first) = slots.first() else {
+        return;
+    };
+    let mut differ = 0;
+    for (i, (slot, _)) in first.iter().enumerate() {
+        let colors = slots.iter().map(|s| s[i].1).collect::<Vec<_>>();
+        let delta = max_delta_e(&colors);
+        let hl = delta > threshold;
+        if hl {
+            differ += 1;
+            print!("\x1b[1;33m{slot:<SLOT_WIDTH$}{RESET}");
+        } else {
+            print!("\x1b[2m{slot:<SLOT_WIDTH$}{RESET}");
+        }
+        for c in &colors {
+            let hex = c.to_string();
+            print!("{}    {RESET} {hex:<w$}", c.ansi_bg(), w = col - 5);
+        }
+        if hl {
+            println!("\x1b[1;33m{delta:>5.1}{RESET}");
+        } else {
+            println!("\x1b[2m{delta:>5.1}{RESET}");
+        }
+    }
+    println!(
+        "\n{differ} of {} slots differ by more than ΔE {threshold}",
+        first.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(hexes: &[&str]) -> Vec<Color> {
+        hexes
+            .iter()
+            .map(|h| Color::from_hex_str(h).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn max_delta_e_is_the_largest_pair() {
+        assert_eq!(max_delta_e(&colors(&["#282828", "#282828"])), 0.0);
+        let all = max_delta_e(&colors(&["#ffffff", "#fefefe", "#000000"]));
+        assert!((all - 100.0).abs() < 0.01, "{all}");
+        assert_eq!(max_delta_e(&[]), 0.0);
+    }
+}
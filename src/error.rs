@@ -0,0 +1,162 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors of the library and the command line, one variant per class with
+/// its own exit code.
+#[derive(Debug)]
+pub enum Error {
+    /// A theme value that cannot be used: a malformed color, an unknown
+    /// palette reference or style.
+    Theme {
+        theme: Option<String>,
+        file: Option<PathBuf>,
+        /// Dotted path of the value, e.g. `colors.bright.red` or
+        /// `colors.background[2]`.
+        slot: String,
+        value: Option<String>,
+        reason: String,
+    },
+    /// A config file that does not parse or cannot be edited.
+    Config {
+        path: Option<PathBuf>,
+        reason: String,
+    },
+    /// Reading or writing a file or the terminal failed.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// Bad command line input: an unknown option value, theme or history step.
+    Usage(String),
+    /// Something tvibe needs is missing: the home directory, fonts, a
+    /// terminal.
+    Unavailable(String),
+}
+
+impl Error {
+    pub fn theme(slot: &str, value: Option<&str>, reason: impl fmt::Display) -> Self {
+        Self::Theme {
+            theme: None,
+            file: None,
+            slot: slot.into(),
+            value: value.map(String::from),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn config(reason: impl fmt::Display) -> Self {
+        Self::Config {
+            path: None,
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Prepends `segment` to the slot path of a theme error, `[n]` indices
+    /// attach without a dot.
+    pub fn at(mut self, segment: &str) -> Self {
+        if let Self::Theme { slot, .. } = &mut self {
+            *slot = match slot.as_str() {
+                "" => segment.into(),
+                s if s.starts_with('[') => format!("{segment}{s}"),
+                s => format!("{segment}.{s}"),
+            };
+        }
+        self
+    }
+
+    /// Names the theme of a theme error unless it is already named.
+    pub fn in_theme(mut self, name: Option<&str>) -> Self {
+        if let Self::Theme { theme, .. } = &mut self
+            && theme.is_none()
+        {
+            *theme = name.map(String::from);
+        }
+        self
+    }
+
+    /// Records the file a theme, config or io error comes from.
+    pub fn in_file(mut self, path: &Path) -> Self {
+        match &mut self {
+            Self::Theme { file, .. } => *file = Some(path.into()),
+            Self::Config { path: p, .. } | Self::Io { path: p, .. } if p.is_none() => {
+                *p = Some(path.into())
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Process exit code of the error class, following `sysexits.h`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => 64,
+            Self::Theme { .. } => 65,
+            Self::Unavailable(_) => 69,
+            Self::Io { .. } => 74,
+            Self::Config { .. } => 78,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Theme {
+                theme,
+                file,
+                slot,
+                value,
+                reason,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}: ", file.display())?;
+                }
+                if let Some(theme) = theme {
+                    write!(f, "theme '{theme}': ")?;
+                }
+                match (slot.as_str(), value) {
+                    ("", Some(v)) => write!(f, "\"{v}\": ")?,
+                    ("", None) => {}
+                    (s, Some(v)) => write!(f, "{s} = \"{v}\": ")?,
+                    (s, None) => write!(f, "{s}: ")?,
+                }
+                write!(f, "{reason}")
+            }
+            Self::Config { path, reason } => match path {
+                Some(p) => write!(f, "{}: {reason}", p.display()),
+                None => write!(f, "{reason}"),
+            },
+            Self::Io { path, source } => match path {
+                Some(p) => write!(f, "{}: {source}", p.display()),
+                None => write!(f, "{source}"),
+            },
+            Self::Usage(msg) | Self::Unavailable(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Self::Io { path: None, source }
+    }
+}
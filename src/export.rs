@@ -0,0 +1,464 @@
+use crate::preview;
+use tvibe::{
+    collection,
+    color::Color,
+    error::Result,
+    models::{PALETTE_SLOTS, PreparedTheme, TERM_COLOR_NAMES, TERM_VARIANTS},
+};
+
+const MARGIN: u32 = 24;
+const GAP: u32 = 8;
+const COLUMNS: u32 = 10;
+const SWATCH_W: u32 = 136;
+const SWATCH_H: u32 = 56;
+// one character cell, the PNG glyphs are 5x7 scaled by GLYPH_SCALE
+const CHAR_W: u32 = 12;
+const LINE_H: u32 = 18;
+const GLYPH_SCALE: u32 = 2;
+const FONT_SIZE: u32 = 15;
+const WIDTH: u32 = MARGIN * 2 + COLUMNS * SWATCH_W + (COLUMNS - 1) * GAP;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Svg,
+    Png,
+    Html,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Svg => "svg",
+            Format::Png => "png",
+            Format::Html => "html",
+        }
+    }
+}
+
+enum Shape {
+    Rect {
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+        fill: Color,
+    },
+    /// One line of text, `(text, color)` spans laid out in fixed-width cells.
+    Text {
+        x: u32,
+        y: u32,
+        spans: Vec<(String, Color)>,
+    },
+}
+
+/// Backend independent swatch sheet, rendered to SVG or rasterized to PNG.
+struct Sheet {
+    height: u32,
+    background: Color,
+    shapes: Vec<Shape>,
+}
+
+impl Sheet {
+    fn text(&mut self, x: u32, y: u32, text: impl Into<String>, color: Color) {
+        self.shapes.push(Shape::Text {
+            x,
+            y,
+            spans: vec![(text.into(), color)],
+        });
+    }
+}
+
+/// Lays out every palette slot as labeled swatches followed by the code
+/// sample.
+fn layout(theme: &PreparedTheme) -> Sheet {
+    let p = theme.palette();
+    let (bg1, bg3, fg1, fg3) = (p.bg[1], p.bg[3], p.fg[1], p.fg[3]);
+    let mut sheet = Sheet {
+        height: 0,
+        background: p.bg[0],
+        shapes: Vec::new(),
+    };
+
+    let mut y = MARGIN;
+    let title = format!(
+        "{} ({})",
+        theme.display_name(),
+        if theme.is_light() { "light" } else { "dark" }
+    );
+    sheet.text(MARGIN, y, title, fg1);
+    y += LINE_H * 2;
+
+    // `Palette::slots` lists the terminal variants, then the shades and diff
+    let slots = p.slots();
+    let (term, rest) = slots.split_at(TERM_VARIANTS.len() * TERM_COLOR_NAMES.len());
+    let (shades, diff) = rest.split_at(PALETTE_SLOTS.len());
+    let groups = TERM_VARIANTS
+        .iter()
+        .zip(term.chunks(TERM_COLOR_NAMES.len()))
+        .map(|(v, s)| (*v, s))
+        .chain([("shades", shades), ("diff", diff)]);
+
+    for (label, slots) in groups {
+        sheet.text(MARGIN, y, label, fg3);
+        y += LINE_H + GAP / 2;
+        for row in slots.chunks(COLUMNS as usize) {
+            for (i, (r, color)) in row.iter().enumerate() {
+                let x = MARGIN + i as u32 * (SWATCH_W + GAP);
+                // outline so swatches matching the page stay visible
+                sheet.shapes.push(Shape::Rect {
+                    x: x - 1,
+                    y: y - 1,
+                    w: SWATCH_W + 2,
+                    h: SWATCH_H + 2,
+                    fill: bg3,
+                });
+                sheet.shapes.push(Shape::Rect {
+                    x,
+                    y,
+                    w: SWATCH_W,
+                    h: SWATCH_H,
+                    fill: *color,
+                });
+                let name = r.split('.').next().unwrap_or(r);
+                let name = if r.starts_with("diff.") { r } else { name };
+                sheet.text(x, y + SWATCH_H + GAP / 2, name, fg1);
+                sheet.text(x, y + SWATCH_H + GAP / 2 + LINE_H, color.to_string(), fg3);
+            }
+            y += SWATCH_H + GAP + LINE_H * 2 + GAP;
+        }
+    }
+
+    let code = preview::code_sample_spans(theme);
+    let box_h = code.len() as u32 * LINE_H + GAP * 2;
+    sheet.shapes.push(Shape::Rect {
+        x: MARGIN,
+        y,
+        w: WIDTH - MARGIN * 2,
+        h: box_h,
+        fill: bg1,
+    });
+    for (i, line) in code.into_iter().enumerate() {
+        sheet.shapes.push(Shape::Text {
+            x: MARGIN + CHAR_W,
+            y: y + GAP + i as u32 * LINE_H,
+            spans: line.into_iter().map(|(t, c)| (t.to_string(), c)).collect(),
+        });
+    }
+    y += box_h + MARGIN;
+
+    sheet.height = y;
+    sheet
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn svg(theme: &PreparedTheme) -> String {
+    let sheet = layout(theme);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{h}\" \
+         viewBox=\"0 0 {WIDTH} {h}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n",
+        h = sheet.height,
+        bg = sheet.background,
+    );
+    for shape in &sheet.shapes {
+        match shape {
+            Shape::Rect { x, y, w, h, fill } => out.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" rx=\"4\" fill=\"{fill}\"/>\n"
+            )),
+            Shape::Text { x, y, spans } => {
+                // y is the top of the line, SVG wants the baseline
+                out.push_str(&format!(
+                    "<text x=\"{x}\" y=\"{}\" xml:space=\"preserve\">",
+                    y + LINE_H - 5
+                ));
+                for (text, fill) in spans {
+                    out.push_str(&format!("<tspan fill=\"{fill}\">{}</tspan>", escape(text)));
+                }
+                out.push_str("</text>\n");
+            }
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Rasterizes the sheet with the built-in bitmap font, no system fonts needed.
+pub fn png(theme: &PreparedTheme) -> Result<Vec<u8>> {
+    let sheet = layout(theme);
+    let (w, h) = (WIDTH, sheet.height);
+    let rgb = |c: Color| {
+        let (r, g, b, _) = c.to_rgba();
+        [r, g, b]
+    };
+    let mut buf = rgb(sheet.background).repeat((w * h) as usize);
+    let mut fill = |x: u32, y: u32, rw: u32, rh: u32, px: [u8; 3]| {
+        for yy in y.min(h)..(y + rh).min(h) {
+            for xx in x.min(w)..(x + rw).min(w) {
+                let i = ((yy * w + xx) * 3) as usize;
+                buf[i..i + 3].copy_from_slice(&px);
+            }
+        }
+    };
+
+    for shape in &sheet.shapes {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                w,
+                h,
+                fill: color,
+            } => fill(*x, *y, *w, *h, rgb(*color)),
+            Shape::Text { x, y, spans } => {
+                let mut cx = *x;
+                let top = y + (LINE_H - 7 * GLYPH_SCALE) / 2;
+                for (text, color) in spans {
+                    let px = rgb(*color);
+                    for ch in text.chars() {
+                        let glyph = glyph(ch);
+                        for (row, bits) in glyph.iter().enumerate() {
+                            for col in 0..5 {
+                                if bits & (0x10 >> col) != 0 {
+                                    fill(
+                                        cx + col * GLYPH_SCALE,
+                                        top + row as u32 * GLYPH_SCALE,
+                                        GLYPH_SCALE,
+                                        GLYPH_SCALE,
+                                        px,
+                                    );
+                                }
+                            }
+                        }
+                        cx += CHAR_W;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, w, h);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let encoded = (|| {
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&buf)?;
+        writer.finish()
+    })();
+    encoded.map_err(std::io::Error::other)?;
+    Ok(out)
+}
+
+pub fn html(theme: &PreparedTheme) -> String {
+    let name = theme.display_name();
+    let bg = theme.palette().bg[0];
+    format!(
+        "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body {{ margin: 0; background: {bg}; }} svg {{ max-width: 100%; height: auto; }}</style>\n\
+         </head>\n<body>\n{}</body>\n</html>\n",
+        escape(name),
+        svg(theme)
+    )
+}
+
+/// Every theme of `collection::LIST` on one HTML page.
+pub fn gallery() -> Result<String> {
+    let mut nav = String::new();
+    let mut sections = String::new();
+    for key in collection::LIST {
+        let theme = collection::by_name(key).prepare()?;
+        let name = escape(theme.display_name());
+        nav.push_str(&format!("<a href=\"#{key}\">{name}</a>\n"));
+        sections.push_str(&format!(
+            "<section id=\"{key}\">\n<h2>{name}</h2>\n{}</section>\n",
+            svg(&theme)
+        ));
+    }
+    Ok(format!(
+        "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>tvibe themes</title>\n\
+         <style>\n\
+         body {{ margin: 0 auto; max-width: 1520px; padding: 16px; background: #1e1e1e; color: #d4d4d4; font-family: sans-serif; }}\n\
+         nav {{ columns: 4; margin-bottom: 32px; }}\n\
+         nav a {{ display: block; color: #9cdcfe; }}\n\
+         svg {{ max-width: 100%; height: auto; }}\n\
+         </style>\n</head>\n<body>\n<h1>tvibe themes</h1>\n<nav>\n{nav}</nav>\n{sections}</body>\n</html>\n"
+    ))
+}
+
+fn glyph(ch: char) -> &'static [u8; 7] {
+    let i = (ch as usize).wrapping_sub(0x20);
+    FONT.get(i).unwrap_or(&FONT[('?' as usize) - 0x20])
+}
+
+// 5x7 bitmap font for printable ASCII, one byte per row, bit 4 is the
+// leftmost column.
+const FONT: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // '!'
+    [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a], // '#'
+    [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04], // '$'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // '%'
+    [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d], // '&'
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // '('
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // ')'
+    [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00], // '*'
+    [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08], // ','
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c], // '.'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // '/'
+    [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e], // '0'
+    [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e], // '1'
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f], // '2'
+    [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e], // '3'
+    [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02], // '4'
+    [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e], // '5'
+    [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e], // '6'
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // '7'
+    [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e], // '8'
+    [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c], // '9'
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00], // ':'
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08], // ';'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // '<'
+    [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00], // '='
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // '>'
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e], // '@'
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11], // 'A'
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e], // 'B'
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e], // 'C'
+    [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c], // 'D'
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f], // 'E'
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10], // 'F'
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f], // 'G'
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11], // 'H'
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e], // 'I'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c], // 'J'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // 'K'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f], // 'L'
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11], // 'M'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // 'N'
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e], // 'O'
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10], // 'P'
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d], // 'Q'
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11], // 'R'
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e], // 'S'
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // 'T'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e], // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04], // 'V'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a], // 'W'
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11], // 'X'
+    [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04], // 'Y'
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f], // 'Z'
+    [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e], // '['
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // '\\'
+    [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e], // ']'
+    [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f], // '_'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f], // 'a'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e], // 'b'
+    [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e], // 'c'
+    [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f], // 'd'
+    [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e], // 'e'
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08], // 'f'
+    [0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 'g'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // 'h'
+    [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e], // 'i'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0c], // 'j'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // 'k'
+    [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e], // 'l'
+    [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11], // 'm'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // 'n'
+    [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e], // 'o'
+    [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10], // 'p'
+    [0x00, 0x00, 0x0d, 0x13, 0x0f, 0x01, 0x01], // 'q'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // 'r'
+    [0x00, 0x00, 0x0e, 0x10, 0x0e, 0x01, 0x1e], // 's'
+    [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06], // 't'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d], // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04], // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a], // 'w'
+    [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11], // 'x'
+    [0x00, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 'y'
+    [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f], // 'z'
+    [0x03, 0x04, 0x04, 0x08, 0x04, 0x04, 0x03], // '{'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // '|'
+    [0x18, 0x04, 0x04, 0x02, 0x04, 0x04, 0x18], // '}'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(display_name: &str) -> PreparedTheme {
+        let mut theme = collection::by_name("rose_pine");
+        theme.display_name = Some(display_name.into());
+        theme.prepare().unwrap()
+    }
+
+    #[test]
+    fn svg_and_html_escape_names() {
+        assert_eq!(
+            escape(r#"<a href="x">R&B</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;R&amp;B&lt;/a&gt;"
+        );
+        let theme = theme("Rosé <Pine> & \"Moon\"");
+        let svg = svg(&theme);
+        assert!(svg.contains(">Rosé &lt;Pine&gt; &amp; &quot;Moon&quot; (dark)</tspan>"));
+        assert!(!svg.contains("<Pine>"));
+        assert_eq!(
+            svg.matches("<text ").count(),
+            svg.matches("</text>").count()
+        );
+
+        let html = html(&theme);
+        assert!(html.contains("<title>Rosé &lt;Pine&gt; &amp; &quot;Moon&quot;</title>"));
+        let bg = theme.palette().bg[0].to_css(false);
+        assert!(html.contains(&format!("background: {bg};")));
+    }
+
+    #[test]
+    fn png_decodes_to_the_laid_out_sheet() {
+        let theme = theme("Rosé Pine");
+        let data = png(&theme).unwrap();
+        let decoder = png::Decoder::new(std::io::Cursor::new(data));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+
+        let sheet = layout(&theme);
+        assert_eq!((info.width, info.height), (WIDTH, sheet.height));
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * info.width + x) * 3) as usize;
+            (buf[i], buf[i + 1], buf[i + 2])
+        };
+        let rgb = |c: Color| {
+            let (r, g, b, _) = c.to_rgba();
+            (r, g, b)
+        };
+        assert_eq!(pixel(0, 0), rgb(theme.palette().bg[0]));
+        // the title, the `base` label and an outline precede the first swatch
+        let Some(Shape::Rect { x, y, w, h, fill }) = sheet.shapes.get(3) else {
+            panic!("no swatch");
+        };
+        assert_eq!(pixel(x + w / 2, y + h / 2), rgb(*fill));
+        assert_eq!(rgb(*fill), rgb(theme.palette().base.black));
+    }
+
+    #[test]
+    fn glyphs_outside_ascii_fall_back() {
+        assert_eq!(glyph('é'), glyph('?'));
+        assert_ne!(glyph('e'), glyph('?'));
+    }
+}
@@ -0,0 +1,198 @@
+//! `extends` resolution. A theme names its parent with
+//! `extends = "gruvbox_dark"` and overrides any part of it: tables merge
+//! key by key, other values replace the parent's.
+
+use crate::error::{Error, Result};
+use std::path::PathBuf;
+
+/// Keys naming the theme itself, they are never inherited. `light` follows
+/// the merged background instead.
+const OWN_KEYS: [&str; 5] = ["name", "display_name", "variant", "light", "extends"];
+
+/// Tables holding one variant of an enum (`Color = ...` or `Colors = [...]`),
+/// an override replaces them as a whole.
+const REPLACED_TABLES: [&str; 3] = ["background", "foreground", "selection"];
+
+/// Text of a theme file and where it was read from.
+pub struct Source {
+    pub text: String,
+    pub file: Option<PathBuf>,
+}
+
+/// Looks up the text of a theme by name, `None` when there is no such theme.
+pub type Lookup<'a> = dyn Fn(&str) -> Result<Option<Source>> + 'a;
+
+/// Parses theme `name` and merges it over its `extends` chain. A cycle or a
+/// missing parent is an error.
+pub fn resolve(name: &str, source: &Lookup) -> Result<toml::Table> {
+    resolve_chain(name, source, &mut Vec::new())
+}
+
+fn resolve_chain(name: &str, source: &Lookup, chain: &mut Vec<String>) -> Result<toml::Table> {
+    if chain.iter().any(|n| n == name) {
+        chain.push(name.into());
+        return Err(
+            Error::theme("extends", None, format!("cycle {}", chain.join(" -> ")))
+                .in_theme(Some(&chain[0])),
+        );
+    }
+    let Some(Source { text, file }) = source(name)? else {
+        return Err(Error::Usage(format!("Unknown theme '{name}'")));
+    };
+    let in_file = |e: Error| match &file {
+        Some(path) => e.in_file(path),
+        None => e,
+    };
+    let mut table = text
+        .parse::<toml::Table>()
+        .map_err(|e| in_file(Error::config(e)))?;
+
+    let Some(parent) = table.remove("extends") else {
+        return Ok(table);
+    };
+    let parent = parent.as_str().ok_or_else(|| {
+        in_file(Error::theme("extends", None, "must be a theme name").in_theme(Some(name)))
+    })?;
+    chain.push(name.into());
+    let mut base = resolve_chain(parent, source, chain).map_err(|e| match e {
+        Error::Usage(msg) => {
+            in_file(Error::theme("extends", Some(parent), msg).in_theme(Some(name)))
+        }
+        e => e,
+    })?;
+    chain.pop();
+
+    for key in OWN_KEYS {
+        base.remove(key);
+    }
+    merge(&mut base, table);
+    Ok(base)
+}
+
+fn merge(dst: &mut toml::Table, src: toml::Table) {
+    for (key, value) in src {
+        match (dst.get_mut(&key), value) {
+            (Some(toml::Value::Table(d)), toml::Value::Table(s))
+                if !REPLACED_TABLES.contains(&key.as_str()) =>
+            {
+                merge(d, s)
+            }
+            (_, value) => {
+                dst.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Source, resolve};
+    use crate::{
+        error::{Error, Result},
+        models::Theme,
+    };
+
+    const THEMES: [(&str, &str); 6] = [
+        (
+            "base",
+            r##"name = "base"
+display_name = "Base"
+variant = "dark"
+author = "someone"
+tags = ["warm"]
+
+[colors]
+comment = "#888888"
+
+[colors.base]
+black = "#1c1c1c"
+red = "#cc0000"
+green = "#00cc00"
+yellow = "#cccc00"
+blue = "#0000cc"
+magenta = "#cc00cc"
+cyan = "#00cccc"
+white = "#cccccc"
+
+[colors.background]
+Colors = ["#0c0c0c", "#101010", "#181818", "#202020", "#282828"]
+
+[colors.foreground]
+Color = "#d0d0d0"
+"##,
+        ),
+        (
+            "child",
+            r##"name = "child"
+extends = "base"
+
+[colors.base]
+red = "#ff0000"
+
+[colors.background]
+Color = "#303030"
+"##,
+        ),
+        ("grandchild", "name = \"grandchild\"\nextends = \"child\"\n"),
+        ("loop_a", "name = \"loop_a\"\nextends = \"loop_b\"\n"),
+        ("loop_b", "name = \"loop_b\"\nextends = \"loop_a\"\n"),
+        ("orphan", "name = \"orphan\"\nextends = \"nowhere\"\n"),
+    ];
+
+    fn lookup(name: &str) -> Result<Option<Source>> {
+        Ok(THEMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, text)| Source {
+                text: text.to_string(),
+                file: None,
+            }))
+    }
+
+    #[test]
+    fn merges_over_the_parent() {
+        let table = resolve("grandchild", &lookup).unwrap();
+        assert!(!table.contains_key("extends"));
+        let theme = toml::Value::Table(table).try_into::<Theme>().unwrap();
+        assert_eq!(theme.name.as_deref(), Some("grandchild"));
+        assert_eq!(theme.display_name, None);
+        assert_eq!(theme.variant, None);
+        assert_eq!(theme.author.as_deref(), Some("someone"));
+        assert_eq!(theme.tags, ["warm"]);
+
+        let theme = theme.prepare().unwrap();
+        let palette = theme.palette();
+        let hex = |c: crate::color::Color| c.to_css(false);
+        assert_eq!(hex(palette.base.red), "#ff0000");
+        assert_eq!(hex(palette.base.green), "#00cc00");
+        assert_eq!(hex(palette.comment), "#888888");
+        // `Color` replaces the parent's `Colors` instead of merging into it
+        assert_eq!(hex(palette.bg[1]), "#303030");
+        assert_ne!(hex(palette.bg[0]), "#0c0c0c");
+    }
+
+    #[test]
+    fn reports_cycles_and_missing_parents() {
+        match resolve("loop_a", &lookup).unwrap_err() {
+            Error::Theme { theme, reason, .. } => {
+                assert_eq!(theme.as_deref(), Some("loop_a"));
+                assert_eq!(reason, "cycle loop_a -> loop_b -> loop_a");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+        match resolve("orphan", &lookup).unwrap_err() {
+            Error::Theme {
+                theme, slot, value, ..
+            } => {
+                assert_eq!(theme.as_deref(), Some("orphan"));
+                assert_eq!(slot, "extends");
+                assert_eq!(value.as_deref(), Some("nowhere"));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+        assert!(matches!(
+            resolve("nowhere", &lookup).unwrap_err(),
+            Error::Usage(_)
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+use crate::error::{Error, Result};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAX_BACKUPS: usize = 5;
+
+/// Writes config files through one place so every target gets the same
+/// safety net: timestamped backups, atomic replace and `--dry-run` diffs.
+#[derive(Debug, Default)]
+pub struct Writer {
+    pub dry_run: bool,
+    pub backup: bool,
+    // dry-run only: path -> (content on disk, rendered content)
+    pending: RefCell<BTreeMap<PathBuf, (Option<String>, String)>>,
+    written: RefCell<Vec<PathBuf>>,
+    // files backed up, a second write must not back up our own output
+    backed_up: RefCell<BTreeSet<PathBuf>>,
+}
+
+impl Writer {
+    pub fn new(dry_run: bool, backup: bool) -> Self {
+        Self {
+            dry_run,
+            backup,
+            ..Default::default()
+        }
+    }
+
+    /// Reads a file, in dry-run mode returns the content rendered so far.
+    pub fn read_to_string(&self, path: &Path) -> Result<String> {
+        if let Some((_, content)) = self.pending.borrow().get(path) {
+            return Ok(content.clone());
+        }
+        std::fs::read_to_string(path).map_err(|e| Error::io(path, e))
+    }
+
+    pub fn write(&self, path: &Path, content: &str) -> Result<()> {
+        if self.dry_run {
+            let mut pending = self.pending.borrow_mut();
+            match pending.get_mut(path) {
+                Some((_, c)) => *c = content.into(),
+                None => {
+                    let old =
+                        if_exists(std::fs::read_to_string(path)).map_err(|e| Error::io(path, e))?;
+                    pending.insert(path.into(), (old, content.into()));
+                }
+            }
+            return Ok(());
+        }
+        self.replace(path, content.as_bytes())
+    }
+
+    /// Writes a binary file such as a font, dry-run mode skips it without
+    /// a diff.
+    pub fn write_bytes(&self, path: &Path, content: &[u8]) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
+        self.replace(path, content)
+    }
+
+    fn replace(&self, path: &Path, content: &[u8]) -> Result<()> {
+        self.written.borrow_mut().push(path.into());
+        let path = resolve_symlink(path);
+        let io = |e| Error::io(&path, e);
+        let old = if_exists(std::fs::read(&path)).map_err(io)?;
+        if old.as_deref() == Some(content) {
+            return Ok(());
+        }
+        if self.backup && old.is_some() && self.backed_up.borrow_mut().insert(path.clone()) {
+            backup(&path).map_err(io)?;
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+        atomic_write(&path, content).map_err(io)
+    }
+
+    /// Files written (or already up to date) since the writer was created.
+    pub fn written(&self) -> Vec<PathBuf> {
+        let mut written = self.written.borrow().clone();
+        written.sort();
+        written.dedup();
+        written
+    }
+
+    /// Prints a unified diff of every file rendered in dry-run mode.
+    pub fn print_diff(&self) {
+        print!("{}", self.diff());
+    }
+
+    fn diff(&self) -> String {
+        let mut out = String::new();
+        for (path, (old, new)) in self.pending.borrow().iter() {
+            let old = old.as_deref().unwrap_or("");
+            if old == new {
+                continue;
+            }
+            let name = path.display().to_string();
+            out += &similar::TextDiff::from_lines(old, new.as_str())
+                .unified_diff()
+                .context_radius(3)
+                .header(&name, &name)
+                .to_string();
+        }
+        out
+    }
+}
+
+/// `None` for a file that does not exist.
+fn if_exists<T>(read: std::io::Result<T>) -> std::io::Result<Option<T>> {
+    match read {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Dotfiles are often symlinks into a repo, replace the target instead of the link.
+fn resolve_symlink(path: &Path) -> PathBuf {
+    match std::fs::symlink_metadata(path) {
+        Ok(m) if m.file_type().is_symlink() => {
+            std::fs::canonicalize(path).unwrap_or_else(|_| path.into())
+        }
+        _ => path.into(),
+    }
+}
+
+/// Replaces `path` through a temporary file next to it, so a crash never
+/// leaves it truncated.
+pub fn atomic_write(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("tvibe");
+    let tmp = path.with_file_name(format!(".{file_name}.tvibe.tmp"));
+
+    let res = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content.as_ref())?;
+        file.sync_all()?;
+        if let Ok(meta) = std::fs::metadata(path) {
+            std::fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        std::fs::rename(&tmp, path)
+    })();
+    if res.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    res
+}
+
+/// Copies `path` to `<name>.<timestamp>.bak`, `<name>.<timestamp>.<n>.bak`
+/// when that exists, and keeps the last few backups.
+fn backup(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("tvibe");
+    let timestamp = format_timestamp(unix_now(), "%Y%m%dT%H%M%S");
+    let backup = (0..)
+        .map(|n| match n {
+            0 => path.with_file_name(format!("{file_name}.{timestamp}.bak")),
+            n => path.with_file_name(format!("{file_name}.{timestamp}.{n}.bak")),
+        })
+        .find(|p| !p.exists())
+        .expect("unbounded");
+    std::fs::copy(path, &backup)?;
+
+    if let Some(dir) = path.parent() {
+        let prefix = format!("{file_name}.");
+        let mut backups = std::fs::read_dir(dir)?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter_map(|n| {
+                let (ts, i) = backup_key(n.strip_prefix(&prefix)?.strip_suffix(".bak")?)?;
+                Some(((ts.to_string(), i), n))
+            })
+            .collect::<Vec<_>>();
+        backups.sort();
+        if backups.len() > MAX_BACKUPS {
+            for (_, name) in &backups[..backups.len() - MAX_BACKUPS] {
+                let _ = std::fs::remove_file(dir.join(name));
+            }
+        }
+    }
+    Ok(backup)
+}
+
+/// Sort key of a backup name between `<name>.` and `.bak`, `None` for
+/// files tvibe did not write.
+fn backup_key(s: &str) -> Option<(&str, u32)> {
+    let (ts, n) = match s.split_once('.') {
+        Some((ts, n)) => (ts, n.parse().ok()?),
+        None => (s, 0),
+    };
+    (ts.len() == 15 && ts.as_bytes()[8] == b'T').then_some((ts, n))
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats unix seconds as UTC, supports `%Y %m %d %H %M %S`.
+pub fn format_timestamp(secs: u64, fmt: &str) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+
+    // civil from days, http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+
+    fmt.replace("%Y", &format!("{y:04}"))
+        .replace("%m", &format!("{m:02}"))
+        .replace("%d", &format!("{d:02}"))
+        .replace("%H", &format!("{:02}", rem / 3600))
+        .replace("%M", &format!("{:02}", rem % 3600 / 60))
+        .replace("%S", &format!("{:02}", rem % 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn backups(dir: &Path) -> Vec<String> {
+        let mut names = std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.unwrap().file_name().into_string().ok())
+            .filter(|n| n.ends_with(".bak"))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn write_backs_up_and_replaces() {
+        let dir = TempDir::new("write");
+        let path = dir.join("init.lua");
+        std::fs::write(&path, "old\n").unwrap();
+
+        let writer = Writer::new(false, true);
+        writer.write(&path, "new\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+        let backups = backups(&dir);
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.join(&backups[0])).unwrap(),
+            "old\n"
+        );
+        assert!(!dir.join(".init.lua.tvibe.tmp").exists());
+        assert_eq!(writer.written(), [path]);
+    }
+
+    #[test]
+    fn unchanged_file_is_left_alone() {
+        let dir = TempDir::new("unchanged");
+        let path = dir.join("init.lua");
+        std::fs::write(&path, "same\n").unwrap();
+
+        let writer = Writer::new(false, true);
+        writer.write(&path, "same\n").unwrap();
+        assert!(backups(&dir).is_empty());
+        assert_eq!(writer.written(), [path]);
+    }
+
+    #[test]
+    fn backups_are_rotated() {
+        let dir = TempDir::new("rotate");
+        let path = dir.join("alacritty.toml");
+        std::fs::write(&path, "old\n").unwrap();
+        for i in 0..MAX_BACKUPS + 2 {
+            std::fs::write(
+                dir.join(format!("alacritty.toml.2020010{i}T000000.bak")),
+                "",
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.join("alacritty.toml.mine.bak"), "").unwrap();
+
+        Writer::new(false, true).write(&path, "new\n").unwrap();
+        let backups = backups(&dir);
+        assert_eq!(backups.len(), MAX_BACKUPS + 1);
+        assert!(backups.contains(&"alacritty.toml.mine.bak".to_string()));
+        assert!(!backups.contains(&"alacritty.toml.20200102T000000.bak".to_string()));
+        assert!(backups.contains(&"alacritty.toml.20200103T000000.bak".to_string()));
+    }
+
+    #[test]
+    fn repeated_writes_keep_the_original() {
+        let dir = TempDir::new("twice");
+        let path = dir.join("alacritty.toml");
+        std::fs::write(&path, "original\n").unwrap();
+
+        // `--font` and then the theme in one run
+        let writer = Writer::new(false, true);
+        writer.write(&path, "font\n").unwrap();
+        writer.write(&path, "font and colors\n").unwrap();
+        let names = backups(&dir);
+        assert_eq!(names.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.join(&names[0])).unwrap(),
+            "original\n"
+        );
+
+        // a second run within the same second gets its own backup
+        Writer::new(false, true).write(&path, "next\n").unwrap();
+        let contents = backups(&dir)
+            .iter()
+            .map(|n| std::fs::read_to_string(dir.join(n)).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(contents.len(), 2);
+        assert!(contents.contains(&"original\n".to_string()));
+        assert!(contents.contains(&"font and colors\n".to_string()));
+    }
+
+    #[test]
+    fn backup_keys_order_same_second_backups() {
+        assert_eq!(backup_key("20200101T000000"), Some(("20200101T000000", 0)));
+        assert_eq!(
+            backup_key("20200101T000000.2"),
+            Some(("20200101T000000", 2))
+        );
+        assert!(backup_key("20200101T000000") < backup_key("20200101T000000.1"));
+        assert_eq!(backup_key("mine"), None);
+        assert_eq!(backup_key("20200101T000000.x"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_file_is_replaced_behind_the_link() {
+        let dir = TempDir::new("symlink");
+        let target = dir.join("dotfiles.toml");
+        let link = dir.join("alacritty.toml");
+        std::fs::write(&target, "old\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        Writer::new(false, false).write(&link, "new\n").unwrap();
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new\n");
+    }
+
+    #[test]
+    fn dry_run_diffs_without_writing() {
+        let dir = TempDir::new("dry-run");
+        let path = dir.join("init.lua");
+        let missing = dir.join("tvibe.lua");
+        std::fs::write(&path, "a\nb\n").unwrap();
+
+        let writer = Writer::new(true, true);
+        writer.write(&path, "a\nc\n").unwrap();
+        assert_eq!(writer.read_to_string(&path).unwrap(), "a\nc\n");
+        writer.write(&path, "a\nd\n").unwrap();
+        writer.write(&missing, "x\n").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb\n");
+        assert!(!missing.exists());
+        assert!(backups(&dir).is_empty());
+        assert!(writer.written().is_empty());
+        let name = path.display();
+        let diff = writer.diff();
+        assert!(diff.contains(&format!(
+            "--- {name}\n+++ {name}\n@@ -1,2 +1,2 @@\n a\n-b\n+d\n"
+        )));
+        assert!(diff.contains("@@ -0,0 +1 @@\n+x\n"));
+    }
+
+    #[test]
+    fn dry_run_skips_unchanged_files() {
+        let dir = TempDir::new("dry-run-same");
+        let path = dir.join("init.lua");
+        std::fs::write(&path, "a\n").unwrap();
+
+        let writer = Writer::new(true, false);
+        writer.write(&path, "a\n").unwrap();
+        assert_eq!(writer.diff(), "");
+    }
+
+    #[test]
+    fn binary_files_skip_dry_run() {
+        let dir = TempDir::new("bytes");
+        let path = dir.join("fonts/Hack/Hack.ttf");
+        let data = [0u8, 1, 0, 0, 0xff];
+
+        let writer = Writer::new(true, true);
+        writer.write_bytes(&path, &data).unwrap();
+        assert!(!path.exists());
+        assert_eq!(writer.diff(), "");
+
+        Writer::new(false, true).write_bytes(&path, &data).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), data);
+    }
+
+    #[test]
+    fn timestamps_are_utc() {
+        assert_eq!(
+            format_timestamp(0, "%Y-%m-%d %H:%M:%S"),
+            "1970-01-01 00:00:00"
+        );
+        assert_eq!(
+            format_timestamp(951_827_696, "%Y%m%dT%H%M%S"),
+            "20000229T123456"
+        );
+    }
+}
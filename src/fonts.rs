@@ -0,0 +1,368 @@
+use std::{
+    collections::HashSet,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use strsim::levenshtein;
+use ttf_parser::{Language, name_id};
+use tvibe::{
+    error::{Error, Result},
+    files::Writer,
+};
+
+const FONT_EXTENSIONS: [&str; 3] = ["ttf", "otf", "ttc"];
+
+/// Directory levels searched below each font directory.
+const MAX_DEPTH: usize = 8;
+
+/// One face of an installed font file, names come from the `name` table.
+#[derive(Debug, Clone)]
+pub struct FontFace {
+    pub family: String,
+    pub style: String,
+    pub path: PathBuf,
+    /// Face index inside a `.ttc` collection, `0` for plain files.
+    pub index: u32,
+}
+
+impl FontFace {
+    pub fn is_nerd_font(&self) -> bool {
+        self.family.contains("Nerd Font")
+            || self
+                .family
+                .split(' ')
+                .any(|w| matches!(w, "NF" | "NFM" | "NFP"))
+    }
+}
+
+/// Directories searched for fonts, the XDG data dirs on Linux.
+pub fn font_dirs(home: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![user_font_dir(home)];
+
+    #[cfg(target_os = "macos")]
+    dirs.push("/Library/Fonts".into());
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        dirs.push(home.join(".fonts"));
+        dirs.push("/usr/share/fonts".into());
+        dirs.push("/usr/local/share/fonts".into());
+    }
+
+    dirs
+}
+
+/// The user's own font directory, where `install` puts fonts.
+pub fn user_font_dir(home: &Path) -> PathBuf {
+    if cfg!(target_os = "macos") {
+        return home.join("Library/Fonts");
+    }
+    match std::env::var_os("XDG_DATA_HOME").filter(|s| !s.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("fonts"),
+        None => home.join(".local/share/fonts"),
+    }
+}
+
+/// Reads every font file below `dirs`, unreadable files are skipped.
+pub fn scan(dirs: &[PathBuf]) -> Vec<FontFace> {
+    let mut faces = font_files(dirs)
+        .iter()
+        .flat_map(|path| read_faces(path))
+        .collect::<Vec<_>>();
+    faces.sort_by(|a, b| (&a.family, &a.style).cmp(&(&b.family, &b.style)));
+    faces
+}
+
+/// Font files below `dirs`. Symlinked directories are followed, but each
+/// directory is read once and at most `MAX_DEPTH` levels down, so links
+/// pointing back up the tree end the walk.
+fn font_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = dirs.iter().map(|d| (d.clone(), 0)).collect::<Vec<_>>();
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(canonical) = std::fs::canonicalize(&dir) else {
+            continue;
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if depth < MAX_DEPTH {
+                    stack.push((path, depth + 1));
+                }
+            } else if is_font_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| FONT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Parses all faces of a font file (several for `.ttc` collections).
+pub fn read_faces(path: &Path) -> Vec<FontFace> {
+    match std::fs::read(path) {
+        Ok(data) => parse_faces(&data, path),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_faces(data: &[u8], path: &Path) -> Vec<FontFace> {
+    let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+    (0..count)
+        .filter_map(|index| {
+            let face = ttf_parser::Face::parse(data, index).ok()?;
+            // typographic names group weights like ExtraBold under one family
+            let family = name(&face, name_id::TYPOGRAPHIC_FAMILY)
+                .or_else(|| name(&face, name_id::FAMILY))?;
+            let style = name(&face, name_id::TYPOGRAPHIC_SUBFAMILY)
+                .or_else(|| name(&face, name_id::SUBFAMILY))
+                .unwrap_or_else(|| "Regular".into());
+            Some(FontFace {
+                family,
+                style,
+                path: path.into(),
+                index,
+            })
+        })
+        .collect()
+}
+
+/// Looks up a `name` table entry, English first.
+fn name(face: &ttf_parser::Face, id: u16) -> Option<String> {
+    let names = face
+        .names()
+        .into_iter()
+        .filter(|n| n.name_id == id)
+        .collect::<Vec<_>>();
+    names
+        .iter()
+        .filter(|n| n.language() == Language::English_UnitedStates)
+        .chain(names.iter())
+        .find_map(|n| n.to_string())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Nerd Font family names, one per family regardless of how many styles
+/// are installed.
+pub fn nerd_families(faces: &[FontFace]) -> Vec<String> {
+    let mut families = faces
+        .iter()
+        .filter(|f| f.is_nerd_font())
+        .map(|f| f.family.clone())
+        .collect::<Vec<_>>();
+    families.sort();
+    families.dedup();
+    families
+}
+
+/// Best family for a fuzzy `query`: exact, then substring, then the
+/// smallest levenshtein distance.
+pub fn find_family<'a>(families: &'a [String], query: &str) -> Option<&'a String> {
+    let query = query.to_lowercase();
+    let score = |s: &str| -> usize {
+        let s = s.to_lowercase();
+        if s == query {
+            0
+        } else if s.contains(&query) {
+            1
+        } else {
+            levenshtein(&s, &query) + 2
+        }
+    };
+    families.iter().min_by_key(|v| score(v))
+}
+
+/// Font files extracted by `install`.
+#[derive(Debug)]
+pub struct Installed {
+    /// Main family of the archive, named after the install directory.
+    pub family: String,
+    pub dir: PathBuf,
+    pub files: Vec<PathBuf>,
+    pub families: Vec<String>,
+}
+
+/// Extracts the TTF/OTF files of a Nerd Fonts release archive into
+/// `<fonts_dir>/<Family>` through `writer`, "Windows Compatible"
+/// duplicates are skipped.
+pub fn install(archive: &Path, fonts_dir: &Path, writer: &Writer) -> Result<Installed> {
+    let file = std::fs::File::open(archive).map_err(|e| Error::io(archive, e))?;
+    let bad_archive = |e: zip::result::ZipError| {
+        Error::Usage(format!("{}: not a font archive: {e}", archive.display()))
+    };
+    let mut zip = zip::ZipArchive::new(file).map_err(bad_archive)?;
+
+    let mut fonts = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(bad_archive)?;
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !entry.is_file() || !is_font_file(&path) || name.contains("Windows Compatible") {
+            continue;
+        }
+        let name = name.to_string();
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .map_err(|e| Error::io(archive, e))?;
+        let faces = parse_faces(&data, Path::new(&name));
+        if !faces.is_empty() {
+            fonts.push((name, data, faces));
+        }
+    }
+
+    let mut families = fonts
+        .iter()
+        .flat_map(|(_, _, faces)| faces.iter().map(|f| f.family.clone()))
+        .collect::<Vec<_>>();
+    families.sort();
+    families.dedup();
+    // "Hack Nerd Font" rather than its "Mono" and "Propo" siblings
+    let family = families
+        .iter()
+        .min_by_key(|f| f.len())
+        .cloned()
+        .ok_or_else(|| {
+            Error::Usage(format!(
+                "{}: no TTF/OTF fonts in archive",
+                archive.display()
+            ))
+        })?;
+
+    let dir = fonts_dir.join(&family);
+    let mut files = Vec::new();
+    for (name, data, _) in &fonts {
+        let path = dir.join(name);
+        writer.write_bytes(&path, data)?;
+        files.push(path);
+    }
+    files.sort();
+    files.dedup();
+
+    Ok(Installed {
+        family,
+        dir,
+        files,
+        families,
+    })
+}
+
+/// Lets fontconfig pick up new files, fine to skip when `fc-cache` is
+/// not installed.
+pub fn refresh_cache(dir: &Path) {
+    #[cfg(target_os = "linux")]
+    {
+        let _ = std::process::Command::new("fc-cache")
+            .arg("-f")
+            .arg(dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = dir;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn face(family: &str, style: &str) -> FontFace {
+        FontFace {
+            family: family.into(),
+            style: style.into(),
+            path: PathBuf::new(),
+            index: 0,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn looping_symlinks_are_read_once() {
+        use std::os::unix::fs::symlink;
+
+        let root = TempDir::new("font-loop");
+        let fonts = root.join("fonts");
+        let other = root.join("other");
+        std::fs::create_dir_all(fonts.join("a")).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(fonts.join("a/Hack.ttf"), "").unwrap();
+        std::fs::write(fonts.join("a/notes.txt"), "").unwrap();
+        std::fs::write(other.join("Iosevka.OTF"), "").unwrap();
+        symlink("..", fonts.join("a/up")).unwrap();
+        symlink("../..", fonts.join("a/up2")).unwrap();
+        symlink(&other, fonts.join("linked")).unwrap();
+
+        let mut files = font_files(&[fonts.clone(), fonts.join("a")]);
+        files.sort();
+        let canonical = files
+            .iter()
+            .map(|f| std::fs::canonicalize(f).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            canonical,
+            [
+                std::fs::canonicalize(fonts.join("a/Hack.ttf")).unwrap(),
+                std::fs::canonicalize(other.join("Iosevka.OTF")).unwrap(),
+            ]
+        );
+        assert!(scan(&[fonts]).is_empty());
+    }
+
+    #[test]
+    fn deep_directories_are_cut_off() {
+        let root = TempDir::new("font-depth");
+        let mut dir = root.to_path_buf();
+        for i in 0..=MAX_DEPTH {
+            dir = dir.join(i.to_string());
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join("f.ttf"), "").unwrap();
+        }
+        assert_eq!(font_files(&[root.to_path_buf()]).len(), MAX_DEPTH);
+    }
+
+    #[test]
+    fn nerd_families_are_deduped() {
+        let faces = [
+            face("Hack Nerd Font", "Regular"),
+            face("Hack Nerd Font", "Bold"),
+            face("Iosevka NFM", "Regular"),
+            face("DejaVu Sans", "Regular"),
+        ];
+        assert!(faces[2].is_nerd_font());
+        assert!(!faces[3].is_nerd_font());
+        assert_eq!(nerd_families(&faces), ["Hack Nerd Font", "Iosevka NFM"]);
+    }
+
+    #[test]
+    fn find_family_prefers_exact_then_substring() {
+        let families = [
+            "Hack Nerd Font".to_string(),
+            "Hack Nerd Font Mono".to_string(),
+            "JetBrainsMono Nerd Font".to_string(),
+        ];
+        let find = |q| find_family(&families, q).map(String::as_str);
+        assert_eq!(find("hack nerd font"), Some("Hack Nerd Font"));
+        assert_eq!(find("jetbrains"), Some("JetBrainsMono Nerd Font"));
+        assert_eq!(find("hakc nerd font mono"), Some("Hack Nerd Font Mono"));
+        assert_eq!(find_family(&[], "hack"), None);
+    }
+}
@@ -0,0 +1,267 @@
+//! Shell commands run around applying a theme, configured in the `[hooks]`
+//! table of the user config. A hook finds the theme in `TVIBE_*`
+//! environment variables and as JSON on stdin.
+
+use serde::Serialize;
+use std::{
+    io::{self, Read, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    time::{Duration, Instant},
+};
+use tvibe::{
+    error::{Error, Result},
+    models::{Palette, PreparedTheme, TermPalette},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// What a hook reads on stdin.
+#[derive(Serialize)]
+struct Input<'a> {
+    name: &'a str,
+    display_name: &'a str,
+    light: bool,
+    /// The target just written, for per-target hooks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<&'a str>,
+    palette: &'a Palette,
+}
+
+/// `TVIBE_THEME`, `TVIBE_LIGHT` (`true` or `false`), `TVIBE_BG`, `TVIBE_FG`
+/// and `TVIBE_COLOR0` to `TVIBE_COLOR17`: the normal and bright terminal
+/// colors, then orange and pink. Per-target hooks also get `TVIBE_TARGET`.
+pub fn env(theme: &PreparedTheme, target: Option<&str>) -> Vec<(String, String)> {
+    let p = theme.palette();
+    let mut env = vec![
+        ("TVIBE_THEME".into(), theme.name().into()),
+        ("TVIBE_LIGHT".into(), theme.is_light().to_string()),
+        ("TVIBE_BG".into(), p.bg[1].to_string()),
+        ("TVIBE_FG".into(), p.fg[1].to_string()),
+    ];
+    let ansi = |t: &TermPalette| {
+        [
+            t.black, t.red, t.green, t.yellow, t.blue, t.magenta, t.cyan, t.white,
+        ]
+    };
+    let colors = ansi(&p.base)
+        .into_iter()
+        .chain(ansi(&p.bright))
+        .chain([p.base.orange, p.base.pink]);
+    for (i, color) in colors.enumerate() {
+        env.push((format!("TVIBE_COLOR{i}"), color.to_string()));
+    }
+    if let Some(target) = target {
+        env.push(("TVIBE_TARGET".into(), target.into()));
+    }
+    env
+}
+
+/// Runs `command` with `sh -c`, passing `theme` as described in the module
+/// docs. A non-zero exit is an error, so is running longer than `timeout`,
+/// which kills the command.
+pub fn run(
+    command: &str,
+    theme: &PreparedTheme,
+    target: Option<&str>,
+    timeout: Duration,
+) -> Result<()> {
+    let failed =
+        |reason: &dyn std::fmt::Display| Error::Unavailable(format!("hook '{command}': {reason}"));
+    let input = serde_json::to_string(&Input {
+        name: theme.name(),
+        display_name: theme.display_name(),
+        light: theme.is_light(),
+        target,
+        palette: theme.palette(),
+    })
+    .map_err(|e| failed(&e))?;
+
+    let mut sh = Command::new("sh");
+    sh.arg("-c").arg(command).envs(env(theme, target));
+    let (status, _) =
+        execute(&mut sh, input.into_bytes(), timeout, false).map_err(|e| failed(&e))?;
+    if !status.success() {
+        return Err(failed(&status));
+    }
+    Ok(())
+}
+
+/// Spawns `command` with `input` on stdin and waits for it, killing it and
+/// whatever it started after `timeout`. Its stdout is returned with
+/// `capture` and passed through otherwise.
+pub(crate) fn execute(
+    command: &mut Command,
+    input: Vec<u8>,
+    timeout: Duration,
+    capture: bool,
+) -> io::Result<(ExitStatus, Vec<u8>)> {
+    command.stdin(Stdio::piped());
+    if capture {
+        command.stdout(Stdio::piped());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // a group of its own, so that a timeout reaches its children too
+        command.process_group(0);
+    }
+    let mut child = command.spawn()?;
+
+    // both pipes on their own threads, neither can fill up and block
+    let stdin = child.stdin.take();
+    let writer = std::thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            // a command that ignores its input may exit before reading it
+            let _ = stdin.write_all(&input);
+        }
+    });
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let reader = child.stdout.take().map(|mut stdout| {
+        std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stdout.read_to_end(&mut output);
+            let _ = sender.send(output);
+        })
+    });
+
+    let deadline = Instant::now() + timeout;
+    let timed_out = || {
+        io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("timed out after {}s", timeout.as_secs()),
+        )
+    };
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill(&mut child);
+            return Err(timed_out());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+    let _ = writer.join();
+    // whatever the command left running may keep stdout open, the deadline
+    // holds for reading it too
+    let output = match reader {
+        Some(_) => {
+            match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(output) => output,
+                Err(_) => {
+                    kill(&mut child);
+                    return Err(timed_out());
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+    Ok((status, output))
+}
+
+/// Kills `child` and, on unix, the rest of its process group.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: kill(2) takes no pointers, the group is the one `execute`
+    // created for the child
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    /// The theme the hook and plugin tests pass around.
+    pub(crate) fn theme() -> PreparedTheme {
+        tvibe::collection::by_name("gruvbox_dark")
+            .prepare()
+            .unwrap()
+    }
+
+    #[test]
+    fn env_names_theme_and_colors() {
+        let theme = theme();
+        let env = env(&theme, Some("nvim"));
+        let get = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+        assert_eq!(get("TVIBE_THEME"), Some("gruvbox_dark"));
+        assert_eq!(get("TVIBE_LIGHT"), Some("false"));
+        assert_eq!(get("TVIBE_TARGET"), Some("nvim"));
+        let red = theme.palette().base.red.to_string();
+        assert_eq!(get("TVIBE_COLOR1"), Some(red.as_str()));
+        assert!(get("TVIBE_COLOR17").is_some() && get("TVIBE_COLOR18").is_none());
+        assert!(
+            super::env(&theme, None)
+                .iter()
+                .all(|(k, _)| k != "TVIBE_TARGET")
+        );
+    }
+
+    #[test]
+    fn run_passes_json_on_stdin() {
+        let dir = TempDir::new("hook-stdin");
+        let out = dir.join("input.json");
+        let command = format!(
+            "cat > '{}' && test \"$TVIBE_TARGET\" = lualine",
+            out.display()
+        );
+        run(&command, &theme(), Some("lualine"), Duration::from_secs(5)).unwrap();
+
+        let input: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(input["name"], "gruvbox_dark");
+        assert_eq!(input["target"], "lualine");
+        assert_eq!(input["light"], false);
+        assert!(
+            input["palette"]["base"]["red"]
+                .as_str()
+                .unwrap()
+                .starts_with('#')
+        );
+    }
+
+    #[test]
+    fn run_reports_failures() {
+        let err = run("exit 3", &theme(), None, Duration::from_secs(5)).unwrap_err();
+        assert!(matches!(err, Error::Unavailable(_)));
+        assert!(err.to_string().contains("hook 'exit 3'"), "{err}");
+    }
+
+    #[test]
+    fn timeout_kills_the_whole_group() {
+        let dir = TempDir::new("hook-timeout");
+        let marker = dir.join("marker");
+        // the background job outlives sh unless its group is killed
+        let script = format!("(sleep 0.5; touch '{}') & wait", marker.display());
+        let mut sh = Command::new("sh");
+        sh.arg("-c").arg(&script);
+        let start = Instant::now();
+        let err = execute(&mut sh, Vec::new(), Duration::from_millis(100), true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_millis(450));
+
+        std::thread::sleep(Duration::from_millis(800));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn timeout_covers_output_held_open_after_exit() {
+        // sh exits at once, the background job keeps its stdout open
+        let mut sh = Command::new("sh");
+        sh.arg("-c").arg("sleep 5 & echo started");
+        let start = Instant::now();
+        let err = execute(&mut sh, Vec::new(), Duration::from_millis(200), true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        let mut sh = Command::new("sh");
+        sh.arg("-c").arg("echo done");
+        let (status, output) = execute(&mut sh, Vec::new(), Duration::from_secs(5), true).unwrap();
+        assert!(status.success());
+        assert_eq!(output, b"done\n");
+    }
+}
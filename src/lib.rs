@@ -0,0 +1,33 @@
+//! Terminal and editor themes: the built-in collection, palette math, the
+//! Neovim and Alacritty renderers and the functions applying them to config
+//! files. The `tvibe` binary is a thin command line over this crate.
+//!
+//! ```no_run
+//! use tvibe::{apply, collection, files::Writer};
+//!
+//! let theme = collection::search("tokyo")?.prepare()?;
+//! println!("{}", tvibe::templ::nvim(&theme));
+//!
+//! let targets = apply::Targets::in_home(&std::env::home_dir().unwrap());
+//! for (target, res) in apply::apply(&theme, &targets, &Writer::new(true, false)) {
+//!     println!("{target}: {}", if res.is_ok() { "ok" } else { "failed" });
+//! }
+//! # Ok::<(), tvibe::error::Error>(())
+//! ```
+//!
+//! The public API is `color`, `models`, `collection`, `templ`, `apply`,
+//! `files` and `error`; fonts, hooks, plugins, history and export belong to
+//! the binary.
+//! The theme TOML format (the [`models::Theme`] fields) follows semver, see
+//! the README for what that covers.
+
+pub mod apply;
+pub mod collection;
+pub mod color;
+pub mod error;
+mod extends;
+pub mod files;
+pub mod models;
+pub mod templ;
+#[cfg(test)]
+mod test_util;
@@ -1,14 +1,34 @@
-mod collection;
-mod color;
-mod models;
-mod templ;
-use clap::Parser;
-use rand::seq::IndexedRandom;
-use std::{fmt::Write, io::BufRead, path::PathBuf, process::exit};
-use strsim::levenshtein;
-
-const DEFAULT_NVIM_CONFIG_PATH: &str = ".config/nvim/init.lua";
-const DEFAULT_ALACRITTY_CONFIG_PATH: &str = ".config/alacritty/alacritty.toml";
+mod compare;
+mod export;
+mod fonts;
+mod hooks;
+mod pick;
+mod plugins;
+mod preview;
+mod raster;
+mod state;
+#[cfg(test)]
+mod test_util;
+use clap::{Parser, Subcommand};
+use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::exit,
+};
+use tvibe::{
+    apply,
+    collection::{self, Collection},
+    error::{Error, Result},
+    files, models,
+};
+
+const DEFAULT_CONFIG_DIR: &str = ".config";
+const DEFAULT_USER_CONFIG_PATH: &str = "tvibe/config.toml";
+const DEFAULT_USER_THEMES_PATH: &str = "tvibe/themes";
+const DEFAULT_STATE_DIR: &str = ".local/state";
+const DEFAULT_DATA_DIR: &str = ".local/share";
+const DEFAULT_PLUGIN_DIR: &str = "tvibe/plugins";
 
 fn supports_truecolor() -> bool {
     matches!(
@@ -18,148 +38,649 @@ fn supports_truecolor() -> bool {
 }
 
 #[inline(always)]
-fn home_dir() -> PathBuf {
-    std::env::home_dir().expect("Unable to determine home directory")
+fn home_dir() -> Result<PathBuf> {
+    std::env::home_dir()
+        .filter(|p| !p.as_os_str().is_empty())
+        .ok_or_else(|| Error::Unavailable("Unable to determine home directory".into()))
 }
 
-#[inline(always)]
-fn nvim_config_path() -> PathBuf {
-    home_dir().join(DEFAULT_NVIM_CONFIG_PATH)
+/// `value` of an XDG base directory variable when it is an absolute path,
+/// `default` below the home directory otherwise.
+fn xdg_dir(value: Option<OsString>, default: &str) -> Result<PathBuf> {
+    match value.map(PathBuf::from).filter(|p| p.is_absolute()) {
+        Some(dir) => Ok(dir),
+        None => Ok(home_dir()?.join(default)),
+    }
 }
 
-#[inline(always)]
-fn alacritty_config_path() -> PathBuf {
-    home_dir().join(DEFAULT_ALACRITTY_CONFIG_PATH)
+fn config_dir() -> Result<PathBuf> {
+    xdg_dir(std::env::var_os("XDG_CONFIG_HOME"), DEFAULT_CONFIG_DIR)
 }
 
-fn apply_theme_to_nvim(theme: &mut models::Theme) -> Result<(), Box<dyn std::error::Error>> {
-    const START_MARK: &str = "-- ====THEMESYNCSTARTBLOCK====";
-    const END_MARK: &str = "-- ====THEMESYNCENDBLOCK====";
+/// `TVIBE_CONFIG` when set, which must then exist.
+fn env_config_path() -> Option<PathBuf> {
+    std::env::var_os("TVIBE_CONFIG")
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+}
 
-    let content = templ::nvim(theme);
-    let path = nvim_config_path();
+fn user_config_path() -> Result<PathBuf> {
+    match env_config_path() {
+        Some(path) => Ok(path),
+        None => Ok(config_dir()?.join(DEFAULT_USER_CONFIG_PATH)),
+    }
+}
 
-    let file = std::fs::File::open(&path).unwrap();
-    let reader = std::io::BufReader::new(file);
+/// Target config files: the defaults below the config directory, then
+/// `paths` and `targets` of the user config, then the path flags. Also
+/// returns the plugin directory, see `find_plugins`.
+fn target_paths(cli: &Cli, user_config: &models::UserConfig) -> Result<(apply::Targets, PathBuf)> {
+    Ok(resolve_targets(
+        cli,
+        user_config,
+        &home_dir()?,
+        &config_dir()?,
+        &data_dir()?,
+    ))
+}
 
-    let mut buf = String::new();
-    let mut lines = reader.lines();
-    let mut inserted = false;
+/// `target_paths` for the given home, config and data directories.
+fn resolve_targets(
+    cli: &Cli,
+    user_config: &models::UserConfig,
+    home: &Path,
+    config_dir: &Path,
+    data_dir: &Path,
+) -> (apply::Targets, PathBuf) {
+    let mut targets = apply::Targets::in_config_dir(config_dir);
+    let paths = user_config.paths.clone().unwrap_or_default();
+    let expand = |path: PathBuf| match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path,
+    };
+    if let Some(path) = cli.nvim_path.clone().or(paths.nvim.map(expand)) {
+        targets.nvim_config = path;
+    }
+    if let Some(path) = cli.lualine_path.clone().or(paths.lualine.map(expand)) {
+        targets.lualine_theme = path;
+    }
+    if let Some(path) = cli.alacritty_path.clone().or(paths.alacritty.map(expand)) {
+        targets.alacritty_config = path;
+    }
+    targets.enabled = user_config.targets.clone();
+    let plugin_dir = paths
+        .plugins
+        .map(expand)
+        .unwrap_or_else(|| data_dir.join(DEFAULT_PLUGIN_DIR));
+    (targets, plugin_dir)
+}
 
-    while let Some(line) = lines.next() {
-        let line = line?;
-        writeln!(&mut buf, "{}", &line)?;
-        if line == START_MARK {
-            writeln!(&mut buf, "{}", &content)?;
-            inserted = true;
-            break;
+/// Plugins of the plugin directory and `PATH`, looked up only when a theme
+/// is applied. Every name in `targets` of the user config must be a
+/// built-in target or one of them.
+fn find_plugins(targets: &apply::Targets, plugin_dir: &Path) -> Result<Vec<plugins::Plugin>> {
+    let plugins = plugins::discover(&plugins::search_dirs(plugin_dir));
+    if let Some(enabled) = targets.enabled.as_ref() {
+        let known = apply::TARGETS
+            .into_iter()
+            .chain(plugins.iter().map(|p| p.name.as_str()))
+            .collect::<Vec<_>>();
+        if let Some(n) = enabled.iter().find(|n| !known.contains(&n.as_str())) {
+            return Err(Error::config(format!(
+                "unknown target '{n}', expected one of {}",
+                known.join(", ")
+            ))
+            .in_file(&user_config_path()?));
         }
     }
-    if inserted {
-        let mut replace_buf = String::new();
-        let mut found_end = false;
-        while let Some(line) = lines.next() {
-            let line = line?;
-            writeln!(&mut replace_buf, "{}", &line)?;
-            if line == END_MARK {
-                found_end = true;
-                break;
-            }
-        }
-        if found_end {
-            writeln!(&mut buf, "{}", &END_MARK)?;
-        } else {
-            writeln!(&mut buf, "{}", &replace_buf)?;
-        }
-        while let Some(line) = lines.next() {
-            let line = line?;
-            writeln!(&mut buf, "{}", &line)?;
-        }
-    } else {
-        writeln!(&mut buf, "\n{START_MARK}\n{content}\n{END_MARK}")?;
+    Ok(plugins)
+}
+
+fn data_dir() -> Result<PathBuf> {
+    xdg_dir(std::env::var_os("XDG_DATA_HOME"), DEFAULT_DATA_DIR)
+}
+
+fn history_path() -> Result<PathBuf> {
+    let state_dir = xdg_dir(std::env::var_os("XDG_STATE_HOME"), DEFAULT_STATE_DIR)?;
+    Ok(state_dir.join("tvibe/history.toml"))
+}
+
+fn load_user_config() -> Result<models::UserConfig> {
+    read_user_config(&user_config_path()?, env_config_path().is_some())
+}
+
+/// Parses the user config at `path`. A missing file is the default config
+/// unless it is `required`, as a path given by `TVIBE_CONFIG` is.
+fn read_user_config(path: &Path, required: bool) -> Result<models::UserConfig> {
+    if !required && !path.exists() {
+        return Ok(Default::default());
     }
+    let buff = std::fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let config =
+        toml::from_str::<models::UserConfig>(&buff).map_err(|e| Error::config(e).in_file(path))?;
+    if let Some(size) = config.font.as_ref().and_then(|f| f.size) {
+        models::FontConfig::check_size(size)
+            .map_err(|e| Error::Usage(format!("{}: {e}", path.display())))?;
+    }
+    Ok(config)
+}
 
-    std::fs::write(&path, &buf)?;
-    Ok(())
+/// `--font-size`, a positive number of points.
+fn parse_font_size(s: &str) -> Result<f32> {
+    let size = s.parse::<f32>().map_err(|e| Error::Usage(e.to_string()))?;
+    models::FontConfig::check_size(size)
 }
 
-#[inline(always)]
-fn load_alacritty_config() -> Result<models::alacritty::Config, Box<dyn std::error::Error>> {
-    let buff = std::fs::read_to_string(alacritty_config_path())?;
-    Ok(toml::from_str::<models::alacritty::Config>(&buff)?)
+/// Built-in themes plus the user's own from `~/.config/tvibe/themes`.
+fn load_collection() -> Result<Collection> {
+    Collection::with_user_dir(&config_dir()?.join(DEFAULT_USER_THEMES_PATH))
 }
 
-#[inline(always)]
-fn save_alacritty_config(
-    config: &models::alacritty::Config,
-) -> Result<models::alacritty::Config, Box<dyn std::error::Error>> {
-    let buff = toml::to_string_pretty(&config)?;
-    std::fs::write(alacritty_config_path(), &buff)?;
-    Ok(toml::from_str::<models::alacritty::Config>(&buff)?)
-}
-
-fn apply_theme_to_alacritty(theme: &mut models::Theme) -> Result<(), Box<dyn std::error::Error>> {
-    let mut config = load_alacritty_config()?;
-    config.replace_colors_from_theme(theme.get_or_insert_colors());
-    save_alacritty_config(&config)?;
-    Ok(())
+/// Installed Nerd Font family best matching `query`, with its styles.
+fn find_font(query: &str) -> Result<apply::FontFamily> {
+    let faces = fonts::scan(&fonts::font_dirs(&home_dir()?));
+    let families = fonts::nerd_families(&faces);
+    if families.is_empty() {
+        return Err(Error::Unavailable(
+            "No Nerd Fonts found on your system".into(),
+        ));
+    }
+    let name = fonts::find_family(&families, query)
+        .ok_or_else(|| Error::Usage(format!("No matching font found for query '{query}'")))?;
+    Ok(apply::FontFamily {
+        name: name.clone(),
+        styles: faces
+            .iter()
+            .filter(|f| &f.family == name)
+            .map(|f| f.style.clone())
+            .collect(),
+    })
 }
 
-fn list_nerd_fonts() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let mut fonts = Vec::new();
+fn list_nerd_fonts() -> Result<Vec<String>> {
+    let faces = fonts::scan(&fonts::font_dirs(&home_dir()?));
+    Ok(fonts::nerd_families(&faces))
+}
+
+/// Runs hook `commands` in order, `--dry-run` only prints them. Failures
+/// are reported under `label` and flagged, returns whether all succeeded.
+fn run_hooks(
+    label: &str,
+    commands: &[String],
+    theme: &models::PreparedTheme,
+    target: Option<&str>,
+    hooks: &models::HooksConfig,
+    writer: &files::Writer,
+    has_error: &mut bool,
+) -> bool {
+    let mut ok = true;
+    for command in commands {
+        if writer.dry_run {
+            println!("would run: {command}");
+        } else if let Err(e) = hooks::run(command, theme, target, hooks.timeout()) {
+            *has_error = true;
+            ok = false;
+            eprintln!("{label}: {e}");
+        }
+    }
+    ok
+}
 
-    #[cfg(target_os = "macos")]
+/// Applies a prepared theme to every enabled target found on the system,
+/// plugins included, between the pre- and post-apply hooks. Returns the
+/// names of the targets written. Errors are reported and flagged, a failed
+/// pre-apply hook leaves every target alone.
+fn apply_theme(
+    theme: &models::PreparedTheme,
+    targets: &apply::Targets,
+    plugins: &[plugins::Plugin],
+    user_config: &models::UserConfig,
+    writer: &files::Writer,
+    has_error: &mut bool,
+) -> Vec<String> {
+    let hooks = user_config.hooks.clone().unwrap_or_default();
+    let run = |label: &str, commands: &[String], target: Option<&str>, has_error: &mut bool| {
+        run_hooks(label, commands, theme, target, &hooks, writer, has_error)
+    };
+    let mut applied = Vec::new();
+    // the first failure cancels the apply, later commands are not run
+    let pre_apply = hooks
+        .pre_apply
+        .iter()
+        .all(|c| run("pre_apply", std::slice::from_ref(c), None, has_error));
+    if !pre_apply {
+        eprintln!("pre_apply: theme not applied");
+        return applied;
+    }
+    let plugin_results = plugins
+        .iter()
+        .filter(|p| targets.is_enabled(&p.name))
+        .map(|p| {
+            (
+                p.name.clone(),
+                plugins::apply(p, theme, writer, hooks.timeout()),
+            )
+        });
+    for (target, res) in apply::apply(theme, targets, writer)
+        .into_iter()
+        .chain(plugin_results)
     {
-        let path = home_dir().join("Library/Fonts");
-        for entry in std::fs::read_dir(path)? {
-            let path = entry?.path();
-            if !path.is_file() {
-                continue;
+        match res {
+            Ok(()) => {
+                run(&target, hooks.for_target(&target), Some(&target), has_error);
+                applied.push(target);
             }
-
-            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                if let Some((name, _)) = file_name.split_once('-') {
-                    if name.contains("NerdFont") {
-                        let name = name
-                            .replace("NerdFont", " Nerd Font ")
-                            .replace(" ", " ")
-                            .trim()
-                            .to_string();
-                        fonts.push(name);
-                    }
-                }
+            Err(e) => {
+                *has_error = true;
+                eprintln!("{target}: {e}");
             }
         }
     }
+    run("post_apply", &hooks.post_apply, None, has_error);
+    applied
+}
 
-    #[cfg(target_os = "linux")]
+fn apply_and_record(
+    theme: &models::PreparedTheme,
+    targets: &apply::Targets,
+    plugins: &[plugins::Plugin],
+    user_config: &models::UserConfig,
+    writer: &files::Writer,
+    has_error: &mut bool,
+) {
+    let applied = apply_theme(theme, targets, plugins, user_config, writer, has_error);
+    if !writer.dry_run
+        && !applied.is_empty()
+        && let Err(e) = record_history(theme, targets, &applied, writer)
     {
-        // TODO:
-        println!("list_nerd_fonts: not implemented for Linux");
+        eprintln!("history: {e}");
     }
+}
 
-    fonts.sort();
-    fonts.dedup();
-    Ok(fonts)
+/// Whether `[font]` of the user config is applied along with a theme: it
+/// is set and alacritty is an enabled target with a config file.
+fn has_default_font(targets: &apply::Targets, user_config: &models::UserConfig) -> bool {
+    user_config.font.as_ref().is_some_and(|f| !f.is_empty())
+        && targets.is_enabled("alacritty")
+        && targets.alacritty_config.exists()
 }
 
-fn set_alacritty_font(query: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let fonts = list_nerd_fonts()?;
-    if fonts.is_empty() {
-        return Err("No Nerd Fonts found on your system".into());
+/// Sets the alacritty font of the font flags, `[font]` of the user config
+/// fills in what they leave out. `query` is the family of `--font` or
+/// `--font-rand`.
+fn set_font(
+    query: Option<String>,
+    cli: &Cli,
+    targets: &apply::Targets,
+    user_config: &models::UserConfig,
+    writer: &files::Writer,
+    has_error: &mut bool,
+) -> Result<()> {
+    let font = user_config.font.clone().unwrap_or_default();
+    let res = query
+        .or(font.family)
+        .map(|q| find_font(&q))
+        .transpose()
+        .and_then(|family| {
+            apply::set_alacritty_font(
+                &targets.alacritty_config,
+                family.as_ref(),
+                cli.font_size.or(font.size),
+                cli.font_style.clone().or(font.style).as_deref(),
+                writer,
+            )
+        });
+    match res {
+        Ok(warnings) => warnings.iter().for_each(|w| eprintln!("font: {w}")),
+        Err(e) => {
+            *has_error = true;
+            eprintln!("{e}");
+        }
     }
+    Ok(())
+}
 
-    let query = query.to_lowercase();
-    let font = fonts
-        .iter()
-        .min_by_key(|v| levenshtein(&v.to_lowercase(), &query))
-        .ok_or_else(|| format!("No matching font found for query '{}'", query))?;
+/// Layers user config and command line style options over the theme's own.
+fn merge_style(
+    theme: &mut models::Theme,
+    user_config: &models::UserConfig,
+    cli_style: &models::StyleOptions,
+) {
+    let style = theme.get_or_insert_style();
+    if let Some(s) = user_config.style.as_ref() {
+        style.merge(s);
+    }
+    style.merge(cli_style);
+}
 
-    let mut config = load_alacritty_config()?;
-    config.set_font_family(font);
-    save_alacritty_config(&config)?;
+fn record_history(
+    theme: &models::PreparedTheme,
+    paths: &apply::Targets,
+    targets: &[String],
+    writer: &files::Writer,
+) -> Result<()> {
+    let path = history_path()?;
+    let mut history = state::History::load(&path)?;
+    let font = apply::load_alacritty_config(&paths.alacritty_config, writer)
+        .ok()
+        .and_then(|c| c.font_family().map(String::from));
+    history.push(state::HistoryEntry {
+        name: theme.name().into(),
+        timestamp: files::unix_now(),
+        font,
+        targets: targets.iter().map(|t| t.to_string()).collect(),
+        files: writer
+            .written()
+            .iter()
+            .filter_map(|p| state::FileHash::of(p).ok())
+            .collect(),
+    });
+    history.save(&path)
+}
 
-    Ok(())
+fn print_history_entry(n: usize, e: &state::HistoryEntry) {
+    println!(
+        "{n:>3}  {}  {:<30} {:<28} {}",
+        files::format_timestamp(e.timestamp, "%Y-%m-%d %H:%M:%S"),
+        e.name,
+        e.font.as_deref().unwrap_or("-"),
+        e.targets.join(","),
+    );
+}
+
+fn run_command(
+    command: &Command,
+    cli: &Cli,
+    user_config: &models::UserConfig,
+    collection: &Collection,
+    targets: &apply::Targets,
+    plugin_dir: &Path,
+    writer: &files::Writer,
+) -> Result<bool> {
+    let cli_style = cli.style_options()?;
+    let mut has_error = false;
+
+    match command {
+        Command::Search { query } => {
+            let query = query.join(" ");
+            let ranked = collection.rank(&cli.themes(collection)?, &query)?;
+            let matches = ranked
+                .iter()
+                .filter(|m| m.score >= collection::LIST_THRESHOLD)
+                .collect::<Vec<_>>();
+            if matches.is_empty() {
+                return Err(collection::no_match(&query, &ranked));
+            }
+            let themes = matches.iter().map(|m| &m.theme).collect::<Vec<_>>();
+            for (m, line) in matches.iter().zip(long_theme_lines(&themes)) {
+                println!("{:.2}  {line}", m.score);
+            }
+        }
+        Command::Compare { themes, threshold } => {
+            let themes = themes
+                .iter()
+                .map(|q| collection.search(&collection.names(), q)?.prepare())
+                .collect::<Result<Vec<_>>>()?;
+            compare::print(&themes, *threshold);
+        }
+        Command::Export {
+            theme,
+            format,
+            gallery,
+            output,
+        } => {
+            let (content, default_name) = match theme {
+                Some(q) if !gallery => {
+                    let theme = collection.search(&collection.names(), q)?.prepare()?;
+                    let content = match format {
+                        export::Format::Svg => export::svg(&theme).into_bytes(),
+                        export::Format::Png => export::png(&theme)?,
+                        export::Format::Html => export::html(&theme).into_bytes(),
+                    };
+                    let name = theme.name.as_deref().unwrap_or("theme");
+                    let name = name.to_lowercase().replace(' ', "_");
+                    (content, format!("{name}.{}", format.extension()))
+                }
+                _ => (
+                    export::gallery()?.into_bytes(),
+                    "tvibe-gallery.html".to_string(),
+                ),
+            };
+            let path = output.clone().unwrap_or_else(|| default_name.into());
+            std::fs::write(&path, content)?;
+            println!("{}", path.display());
+        }
+        Command::Font { command } => match command {
+            FontCommand::Preview { query, protocol } => {
+                let faces = fonts::scan(&fonts::font_dirs(&home_dir()?));
+                let families = fonts::nerd_families(&faces);
+                let no_fonts = || Error::Unavailable("No Nerd Fonts found on your system".into());
+                let family = fonts::find_family(&families, query).ok_or_else(no_fonts)?;
+                let family_faces = faces
+                    .iter()
+                    .filter(|f| &f.family == family)
+                    .collect::<Vec<_>>();
+                let face = family_faces
+                    .iter()
+                    .find(|f| f.style == "Regular")
+                    .or(family_faces.first())
+                    .ok_or_else(no_fonts)?;
+                println!("{} {}", face.family, face.style);
+                let protocol = protocol.unwrap_or_else(raster::Protocol::detect);
+                print!("{}", raster::preview(face, protocol)?);
+            }
+            FontCommand::Install { archive, set } => {
+                let fonts_dir = fonts::user_font_dir(&home_dir()?);
+                let installed = fonts::install(archive, &fonts_dir, writer)?;
+                for file in &installed.files {
+                    println!("{}", file.display());
+                }
+                if !writer.dry_run {
+                    fonts::refresh_cache(&installed.dir);
+                }
+                let verb = if writer.dry_run {
+                    "would install"
+                } else {
+                    "installed"
+                };
+                for family in &installed.families {
+                    println!("{verb}: {family}");
+                }
+                if *set {
+                    let path = &targets.alacritty_config;
+                    let mut config = apply::load_alacritty_config(path, writer)?;
+                    config.set_font_family(&installed.family);
+                    apply::save_alacritty_config(&config, path, writer)?;
+                }
+            }
+        },
+        Command::Current => {
+            let history = state::History::load(&history_path()?)?;
+            let Some(e) = history.current() else {
+                return Err(Error::Usage("No theme has been applied yet".into()));
+            };
+            println!("{}", e.name);
+            println!(
+                "applied: {}",
+                files::format_timestamp(e.timestamp, "%Y-%m-%d %H:%M:%S UTC")
+            );
+            if let Some(font) = e.font.as_ref() {
+                println!("font:    {font}");
+            }
+            println!("targets: {}", e.targets.join(", "));
+            for f in e.files.iter().filter(|f| !f.is_intact()) {
+                println!("modified since applied: {}", f.path.display());
+            }
+        }
+        Command::Pick => {
+            let mut style = user_config.style.clone().unwrap_or_default();
+            style.merge(&cli_style);
+            if let Some(name) = pick::run(collection, Some(&style), cli.dark, cli.light)? {
+                let mut theme = collection.get(name)?;
+                println!("{name}");
+                merge_style(&mut theme, user_config, &cli_style);
+                if has_default_font(targets, user_config) {
+                    set_font(None, cli, targets, user_config, writer, &mut has_error)?;
+                }
+                let theme = theme.prepare()?;
+                let plugins = find_plugins(targets, plugin_dir)?;
+                apply_and_record(
+                    &theme,
+                    targets,
+                    &plugins,
+                    user_config,
+                    writer,
+                    &mut has_error,
+                );
+            }
+        }
+        Command::History { limit } => {
+            let history = state::History::load(&history_path()?)?;
+            for (n, e) in history.entries.iter().rev().take(*limit).enumerate() {
+                print_history_entry(n, e);
+            }
+        }
+        Command::Undo { steps } => {
+            let path = history_path()?;
+            let mut history = state::History::load(&path)?;
+            let Some(entry) = history.back(*steps).cloned() else {
+                return Err(Error::Usage(format!(
+                    "History has only {} entries, cannot go back {steps}",
+                    history.entries.len()
+                )));
+            };
+            if !collection.names().contains(&entry.name.as_str()) {
+                return Err(Error::Usage(format!(
+                    "Theme '{}' is no longer available",
+                    entry.name
+                )));
+            }
+            let mut theme = collection.get(&entry.name)?;
+            println!("{}", entry.name);
+            merge_style(&mut theme, user_config, &cli_style);
+            let theme = theme.prepare()?;
+            let plugins = find_plugins(targets, plugin_dir)?;
+            let alacritty = &targets.alacritty_config;
+            if let Some(font) = entry.font.as_ref()
+                && alacritty.exists()
+            {
+                let res = apply::load_alacritty_config(alacritty, writer).and_then(|mut c| {
+                    c.set_font_family(font);
+                    apply::save_alacritty_config(&c, alacritty, writer)
+                });
+                if let Err(e) = res {
+                    has_error = true;
+                    eprintln!("alacritty: {e}");
+                }
+            }
+            apply_theme(
+                &theme,
+                targets,
+                &plugins,
+                user_config,
+                writer,
+                &mut has_error,
+            );
+            // a failed undo leaves the history as it was, so it can be retried
+            if has_error {
+                eprintln!("history: not rewound, {} was not fully applied", entry.name);
+            } else if !writer.dry_run {
+                history.rewind(*steps);
+                if let Some(last) = history.entries.last_mut() {
+                    last.files = writer
+                        .written()
+                        .iter()
+                        .filter_map(|p| state::FileHash::of(p).ok())
+                        .collect();
+                }
+                history.save(&path)?;
+            }
+        }
+    }
+    Ok(has_error)
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Browse themes in a full-screen picker with live preview
+    Pick,
+
+    /// List themes matching a query by name, tags and metadata, best first
+    Search {
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+    },
+
+    /// Compare the prepared palettes of two or more themes slot by slot
+    Compare {
+        #[arg(required = true, num_args = 2..)]
+        themes: Vec<String>,
+
+        /// Highlight slots whose colors differ by more than this ΔE (CIEDE2000)
+        #[arg(long, default_value_t = 2.0)]
+        threshold: f32,
+    },
+
+    /// Export a theme's swatch sheet as SVG, PNG or HTML
+    Export {
+        #[arg(required_unless_present = "gallery")]
+        theme: Option<String>,
+
+        /// Output format, the gallery is always HTML
+        #[arg(short, long, value_enum, default_value_t = export::Format::Svg)]
+        format: export::Format,
+
+        /// Render every built-in theme into a single HTML page
+        #[arg(long, conflicts_with = "theme")]
+        gallery: bool,
+
+        /// Output file, defaults to `<theme>.<format>` or `tvibe-gallery.html`
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Nerd Font tools
+    Font {
+        #[command(subcommand)]
+        command: FontCommand,
+    },
+
+    /// Show the currently applied theme
+    Current,
+
+    /// List previously applied themes, newest first
+    History {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Re-apply the theme applied N steps before the current one
+    Undo {
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum FontCommand {
+    /// Render a sample of the font matching the query in the terminal
+    Preview {
+        query: String,
+
+        /// Graphics protocol, detected from the terminal by default
+        #[arg(long, value_enum)]
+        protocol: Option<raster::Protocol>,
+    },
+
+    /// Install the fonts of a Nerd Fonts release archive (.zip)
+    Install {
+        archive: PathBuf,
+
+        /// Also set the installed family as the alacritty font
+        #[arg(long)]
+        set: bool,
+    },
 }
 
 #[derive(Parser)]
@@ -174,6 +695,9 @@ Examples:
     tvibe -rdF                  # set rand dark theme and rand font"#
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Apply theme by name (supports fuzzy matching)
     #[arg(short, long)]
     theme: Option<String>,
@@ -182,7 +706,7 @@ struct Cli {
     #[arg(short, long)]
     rand: bool,
 
-    /// When used with --rand or --theme-list, filters to dark themes
+    /// When used with --theme, --rand or --theme-list, filters to dark themes
     #[arg(short, long)]
     dark: bool,
 
@@ -190,10 +714,22 @@ struct Cli {
     #[arg(short, long)]
     light: bool,
 
-    /// List available Nerd Fonts
+    /// Seed for reproducible random theme and font picks
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// List available themes
     #[arg(long)]
     theme_list: bool,
 
+    /// With --theme-list, also show the display name, tags and author
+    #[arg(long, requires = "theme_list")]
+    long: bool,
+
+    /// Only themes with this tag (repeatable), for --theme, --rand and --theme-list
+    #[arg(long, value_name = "TAG")]
+    tag: Vec<String>,
+
     /// Set font family by name (supports fuzzy matching)
     #[arg(short, long)]
     font: Option<String>,
@@ -206,7 +742,15 @@ struct Cli {
     #[arg(long)]
     font_list: bool,
 
-    /// Display the theme's color palette in the terminal without applying it
+    /// Set the alacritty font size in points
+    #[arg(long, value_name = "SIZE", value_parser = parse_font_size)]
+    font_size: Option<f32>,
+
+    /// Set the style of the normal font face (e.g. Regular, Medium, Light)
+    #[arg(long, value_name = "STYLE")]
+    font_style: Option<String>,
+
+    /// Preview the theme (palette, code, diagnostics, diff) without applying it
     #[arg(short, long)]
     show: bool,
 
@@ -217,102 +761,416 @@ struct Cli {
     /// Rust fmt format
     #[arg(long)]
     show_fmt: bool,
-    // /// Alacritty config path
-    // #[arg(short, long)]
-    // alacritty_path: Option<String>,
 
-    // /// Neovim config path
-    // #[arg(short, long)]
-    // nvim_path: Option<String>,
+    /// Print a diff of the changes instead of writing config files
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Do not keep timestamped backups of modified config files
+    #[arg(long)]
+    no_backup: bool,
+
+    /// Transparent editor background and translucent Alacritty window
+    #[arg(long)]
+    transparent: bool,
+
+    /// Dim inactive Neovim windows
+    #[arg(long)]
+    dim_inactive: bool,
+
+    /// Italic comments
+    #[arg(long)]
+    italic_comments: bool,
+
+    /// Bold keywords
+    #[arg(long)]
+    bold_keywords: bool,
+
+    /// Highlight style per category, e.g. --style functions=bold,italic
+    #[arg(long, value_name = "CATEGORY=STYLE")]
+    style: Vec<String>,
+
+    /// Use reverse video for match_paren, visual or search
+    #[arg(long, value_name = "TARGET")]
+    inverse: Vec<String>,
+
+    /// Alacritty config path
+    #[arg(long, value_name = "PATH")]
+    alacritty_path: Option<PathBuf>,
+
+    /// Neovim config path
+    #[arg(long, value_name = "PATH")]
+    nvim_path: Option<PathBuf>,
+
+    /// Lualine theme path
+    #[arg(long, value_name = "PATH")]
+    lualine_path: Option<PathBuf>,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+impl Cli {
+    /// Themes of `collection` left by --dark, --light and --tag.
+    fn themes<'a>(&self, collection: &'a Collection) -> Result<Vec<&'a str>> {
+        let light = if self.dark {
+            Some(false)
+        } else if self.light {
+            Some(true)
+        } else {
+            None
+        };
+        let themes = collection.filter(light, &self.tag)?;
+        if themes.is_empty() {
+            return Err(Error::Usage(format!(
+                "No themes tagged '{}'",
+                self.tag.join("', '")
+            )));
+        }
+        Ok(themes)
+    }
+
+    fn style_options(&self) -> Result<models::StyleOptions> {
+        let mut style = models::StyleOptions::default();
+        if self.transparent {
+            style.transparent = Some(true);
+        }
+        if self.dim_inactive {
+            style.dim_inactive = Some(true);
+        }
+        for target in &self.inverse {
+            style.set_inverse(target)?;
+        }
+        let styles = style.styles.get_or_insert(Default::default());
+        if self.italic_comments {
+            styles.add("comments", "italic")?;
+        }
+        if self.bold_keywords {
+            styles.add("keywords", "bold")?;
+        }
+        for v in &self.style {
+            let (category, attrs) = v.split_once('=').ok_or_else(|| {
+                Error::Usage(format!("Invalid style '{v}', expected CATEGORY=STYLE"))
+            })?;
+            let slot = styles
+                .get_mut(category.trim())
+                .ok_or_else(|| Error::Usage(format!("Unknown style category '{category}'")))?;
+            *slot = Some(attrs.trim().into());
+        }
+        style
+            .validation()
+            .map_err(|e| Error::Usage(format!("--style: {e}")))?;
+        Ok(style)
+    }
+}
+
+/// `--theme-list --long`: key, kind, display name, tags and author.
+fn print_long_theme_list(collection: &Collection, names: &[&str]) -> Result<()> {
+    let themes = names
+        .iter()
+        .map(|n| collection.get(n))
+        .collect::<Result<Vec<_>>>()?;
+    for line in long_theme_lines(&themes.iter().collect::<Vec<_>>()) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// One aligned line per theme: key, kind, display name, tags and author.
+fn long_theme_lines(themes: &[&models::Theme]) -> Vec<String> {
+    let names = themes
+        .iter()
+        .map(|t| t.name.as_deref().unwrap_or("unknown"))
+        .collect::<Vec<_>>();
+    let tags = themes.iter().map(|t| t.tags.join(",")).collect::<Vec<_>>();
+    let name_w = names.iter().map(|n| n.len()).max().unwrap_or(0);
+    let display_w = themes
+        .iter()
+        .map(|t| t.display_name().chars().count())
+        .max()
+        .unwrap_or(0);
+    let tags_w = tags.iter().map(|t| t.len()).max().unwrap_or(0);
+    names
+        .iter()
+        .zip(themes)
+        .zip(&tags)
+        .map(|((name, theme), tags)| {
+            let kind = if theme.is_light() { "light" } else { "dark" };
+            let line = format!(
+                "{name:<name_w$}  {kind:<5}  {:<display_w$}  {tags:<tags_w$}  {}",
+                theme.display_name(),
+                theme.author.as_deref().unwrap_or(""),
+            );
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Names the other variants of the family when `query` was not the exact
+/// theme name, e.g. `-t kanagawa` applies one and mentions the rest.
+fn print_other_variants(collection: &Collection, theme: &models::Theme, query: &str) -> Result<()> {
+    let (Some(name), Some(family)) = (theme.name.as_deref(), theme.family.as_deref()) else {
+        return Ok(());
+    };
+    if name.eq_ignore_ascii_case(query) {
+        return Ok(());
+    }
+    let others = collection
+        .family(family)?
+        .into_iter()
+        .filter(|n| *n != name)
+        .collect::<Vec<_>>();
+    if !others.is_empty() {
+        eprintln!("{family}: also {}", others.join(", "));
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("tvibe: {e}");
+        exit(e.exit_code());
+    }
+}
+
+fn run() -> Result<()> {
     let cli = Cli::parse();
+    let cli_style = cli.style_options()?;
+    let user_config = load_user_config()?;
+    let collection = load_collection()?;
+    let (targets, plugin_dir) = target_paths(&cli, &user_config)?;
+    let writer = files::Writer::new(cli.dry_run, !cli.no_backup);
 
     if !supports_truecolor() {
         println!("Warning: Your terminal does not fully support truecolor");
     }
 
+    if let Some(command) = cli.command.as_ref() {
+        let has_error = run_command(
+            command,
+            &cli,
+            &user_config,
+            &collection,
+            &targets,
+            &plugin_dir,
+            &writer,
+        )?;
+        if cli.dry_run {
+            writer.print_diff();
+        }
+        if has_error {
+            exit(1);
+        }
+        return Ok(());
+    }
+
     if cli.theme_list {
-        {
-            if cli.dark {
-                collection::DARK_LIST.iter()
-            } else if cli.light {
-                collection::LIGHT_LIST.iter()
-            } else {
-                collection::LIST.iter()
-            }
+        let themes = cli.themes(&collection)?;
+        if cli.long {
+            print_long_theme_list(&collection, &themes)?;
+        } else {
+            themes.iter().for_each(|i| println!("{i}"));
         }
-        .for_each(|i| println!("{i}"));
     }
     if cli.font_list {
         list_nerd_fonts()?.iter().for_each(|i| println!("{i}"));
     }
 
     let mut has_error = false;
+    let mut rng = match cli.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rand::rng()),
+    };
 
-    if let Some(query) = cli.font {
-        if let Err(e) = set_alacritty_font(&query) {
-            has_error = true;
-            eprintln!("{}", e);
-        }
-    } else if cli.font_rand {
-        let res = set_alacritty_font(
+    let font_query = if cli.font_rand && cli.font.is_none() {
+        Some(
             list_nerd_fonts()?
-                .choose(&mut rand::rng())
-                .unwrap_or(&"".into()),
-        );
-        if let Err(e) = res {
-            has_error = true;
-            eprintln!("{}", e);
-        }
+                .choose(&mut rng)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    } else {
+        cli.font.clone()
+    };
+    let applies_theme =
+        (cli.theme.is_some() || cli.rand) && !cli.show && !cli.show_toml && !cli.show_fmt;
+    if font_query.is_some()
+        || cli.font_size.is_some()
+        || cli.font_style.is_some()
+        || (applies_theme && has_default_font(&targets, &user_config))
+    {
+        set_font(
+            font_query,
+            &cli,
+            &targets,
+            &user_config,
+            &writer,
+            &mut has_error,
+        )?;
     }
-    let theme = if let Some(query) = cli.theme {
-        Some(collection::search(&query))
+    let theme = if let Some(query) = cli.theme.as_deref() {
+        let theme = collection.search(&cli.themes(&collection)?, query)?;
+        print_other_variants(&collection, &theme, query)?;
+        Some(theme)
     } else if cli.rand {
-        if cli.dark {
-            Some(collection::rand_dark())
-        } else if cli.light {
-            Some(collection::rand_light())
-        } else {
-            Some(collection::rand())
-        }
+        let opts = user_config.random.clone().unwrap_or_default();
+        let history = state::History::load(&history_path()?).unwrap_or_default();
+        let recent = history
+            .entries
+            .iter()
+            .rev()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>();
+        Some(collection.pick(&cli.themes(&collection)?, &mut rng, &opts, &recent)?)
     } else {
         None
     };
     if let Some(mut theme) = theme {
         println!("{}", theme.name.clone().unwrap_or("unknown".into()));
-        theme.prepare()?;
-        // theme.validation()?;
+        merge_style(&mut theme, &user_config, &cli_style);
+        let theme = theme.prepare()?;
         if cli.show {
-            let base_colors = theme.get_or_insert_colors().base.to_vec_colors()?;
-            color::print_palette(&base_colors);
-            toml::to_string_pretty(&theme).unwrap();
+            preview::print(&theme);
         }
         if cli.show_toml {
-            println!("{}", toml::to_string_pretty(&theme)?);
+            println!("{}", toml::to_string_pretty(&theme).map_err(Error::config)?);
         }
         if cli.show_fmt {
-            println!("{:#?}", theme);
+            println!("{:#?}", *theme);
         }
-        if !cli.show && !cli.show_toml && !cli.show_fmt {
-            if nvim_config_path().exists() {
-                if let Err(e) = apply_theme_to_nvim(&mut theme) {
-                    has_error = true;
-                    eprintln!("{}", e);
-                }
-            }
-            if alacritty_config_path().exists() {
-                if let Err(e) = apply_theme_to_alacritty(&mut theme) {
-                    has_error = true;
-                    eprintln!("{}", e);
-                }
-            }
+        if applies_theme {
+            let plugins = find_plugins(&targets, &plugin_dir)?;
+            apply_and_record(
+                &theme,
+                &targets,
+                &plugins,
+                &user_config,
+                &writer,
+                &mut has_error,
+            );
         }
     }
+    if cli.dry_run {
+        writer.print_diff();
+    }
     if has_error {
         exit(1);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn resolve(args: &[&str], config: &str) -> (apply::Targets, PathBuf) {
+        let cli = Cli::parse_from(["tvibe"].iter().chain(args));
+        let user_config = toml::from_str(config).unwrap();
+        resolve_targets(
+            &cli,
+            &user_config,
+            Path::new("/home/me"),
+            Path::new("/home/me/.config"),
+            Path::new("/data"),
+        )
+    }
+
+    #[test]
+    fn targets_default_below_the_config_dir() {
+        let (targets, plugin_dir) = resolve(&[], "");
+        let config = Path::new("/home/me/.config");
+        assert_eq!(targets.nvim_config, config.join("nvim/init.lua"));
+        assert_eq!(
+            targets.lualine_theme,
+            config.join("nvim/lua/lualine/themes/tvibe.lua")
+        );
+        assert_eq!(
+            targets.alacritty_config,
+            config.join("alacritty/alacritty.toml")
+        );
+        assert_eq!(targets.enabled, None);
+        assert_eq!(plugin_dir, Path::new("/data/tvibe/plugins"));
+    }
+
+    #[test]
+    fn config_paths_expand_home_and_yield_to_flags() {
+        let config = r#"targets = ["nvim", "foot"]
+
+[paths]
+nvim = "~/dotfiles/init.lua"
+alacritty = "/etc/alacritty.toml"
+lualine = "relative/tvibe.lua"
+plugins = "~/bin/tvibe"
+"#;
+        let (targets, plugin_dir) = resolve(&[], config);
+        assert_eq!(targets.nvim_config, Path::new("/home/me/dotfiles/init.lua"));
+        assert_eq!(targets.alacritty_config, Path::new("/etc/alacritty.toml"));
+        assert_eq!(targets.lualine_theme, Path::new("relative/tvibe.lua"));
+        assert_eq!(plugin_dir, Path::new("/home/me/bin/tvibe"));
+        assert_eq!(
+            targets.enabled.as_deref(),
+            Some(&["nvim".into(), "foot".into()][..])
+        );
+
+        let (targets, _) = resolve(&["--nvim-path", "/tmp/init.lua"], config);
+        assert_eq!(targets.nvim_config, Path::new("/tmp/init.lua"));
+        assert_eq!(targets.alacritty_config, Path::new("/etc/alacritty.toml"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn enabled_targets_must_be_known() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("plugin-targets");
+        let plugin = dir.join("tvibe-target-foot");
+        std::fs::write(&plugin, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&plugin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut targets = apply::Targets::in_config_dir(&dir);
+
+        targets.enabled = Some(vec!["nvim".into(), "foot".into()]);
+        let plugins = find_plugins(&targets, &dir).unwrap();
+        assert!(plugins.iter().any(|p| p.name == "foot"));
+
+        targets.enabled = Some(vec!["nvim".into(), "tvibe-test-missing".into()]);
+        let err = find_plugins(&targets, &dir).unwrap_err();
+        assert_eq!(err.exit_code(), 78);
+        assert!(
+            err.to_string()
+                .contains("unknown target 'tvibe-test-missing'")
+        );
+    }
+
+    #[test]
+    fn xdg_dirs_must_be_absolute() {
+        assert_eq!(
+            xdg_dir(Some("/xdg/config".into()), DEFAULT_CONFIG_DIR).unwrap(),
+            Path::new("/xdg/config")
+        );
+        let fallback = home_dir().unwrap().join(DEFAULT_CONFIG_DIR);
+        assert_eq!(
+            xdg_dir(Some("xdg/config".into()), DEFAULT_CONFIG_DIR).unwrap(),
+            fallback
+        );
+        assert_eq!(xdg_dir(None, DEFAULT_CONFIG_DIR).unwrap(), fallback);
+    }
+
+    #[test]
+    fn user_config_is_optional_unless_required() {
+        let dir = TempDir::new("user-config");
+        let path = dir.join("config.toml");
+        assert!(read_user_config(&path, false).unwrap().targets.is_none());
+        assert_eq!(read_user_config(&path, true).unwrap_err().exit_code(), 74);
+
+        std::fs::write(&path, "targets = [\"nvim\"]\n").unwrap();
+        let config = read_user_config(&path, true).unwrap();
+        assert_eq!(config.targets, Some(vec!["nvim".to_string()]));
+
+        std::fs::write(&path, "targets = \"nvim\"\n").unwrap();
+        let err = read_user_config(&path, false).unwrap_err();
+        assert_eq!(err.exit_code(), 78);
+        assert!(err.to_string().contains(&path.display().to_string()));
+
+        std::fs::write(&path, "[font]\nsize = -1\n").unwrap();
+        assert_eq!(read_user_config(&path, false).unwrap_err().exit_code(), 64);
+    }
+}
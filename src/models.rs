@@ -1,9 +1,9 @@
-#![allow(dead_code)]
-#![allow(unused_variables)]
-#![allow(unused_imports)]
-
-use crate::color::Color;
+use crate::{
+    color::Color,
+    error::{Error, Result},
+};
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
 
 pub type BackgroundColors = [String; 5];
 pub type ForegroundColors = [String; 4];
@@ -13,6 +13,9 @@ const DEFAULT_BACKGROUND_COLOR: &str = "#000000";
 const DEFAULT_FOREGROUND_COLOR: &str = "#ffffff";
 const DEFAULT_SELECTION_COLOR: &str = "#2a2a2a";
 
+/// L* of the default background above which a theme counts as light.
+const LIGHT_BACKGROUND_LIGHTNESS: f32 = 50.0;
+
 const DEFAULT_SHADE_FACTOR: f32 = 0.15;
 const DEFAULT_COMMENT_BLEND_FACTOR: f32 = 0.4;
 
@@ -105,45 +108,46 @@ impl Default for TermColors {
     }
 }
 
+/// Parses a theme color, errors name `slot`.
+fn hex(slot: &str, value: &str) -> Result<Color> {
+    Color::from_hex_str(value).map_err(|e| e.at(slot))
+}
+
+/// Parses a color that `prepare` fills in, a missing one is an error
+/// rather than a panic.
+fn filled(slot: &str, value: Option<&str>) -> Result<Color> {
+    let value = value.ok_or_else(|| Error::theme(slot, None, "not set"))?;
+    hex(slot, value)
+}
+
 impl TermColors {
-    pub fn shade(&self, factor: Option<f32>) -> Result<Self, String> {
+    pub fn shade(&self, factor: Option<f32>) -> Result<Self> {
         let factor = factor.unwrap_or(DEFAULT_SHADE_FACTOR);
+        let shade = |slot: &str, v: &str| hex(slot, v).map(|c| c.shade(factor).to_css(false));
         Ok(Self {
-            black: Color::from_hex_str(&self.black)?
-                .shade(factor)
-                .to_css(false),
-            red: Color::from_hex_str(&self.red)?.shade(factor).to_css(false),
-            green: Color::from_hex_str(&self.green)?
-                .shade(factor)
-                .to_css(false),
-            yellow: Color::from_hex_str(&self.yellow)?
-                .shade(factor)
-                .to_css(false),
-            blue: Color::from_hex_str(&self.blue)?.shade(factor).to_css(false),
-            magenta: Color::from_hex_str(&self.magenta)?
-                .shade(factor)
-                .to_css(false),
-            cyan: Color::from_hex_str(&self.cyan)?.shade(factor).to_css(false),
-            white: Color::from_hex_str(&self.white)?
-                .shade(factor)
-                .to_css(false),
-            orange: Some(
-                Color::from_hex_str(&self.orange.as_ref().unwrap_or(&self.yellow))?
-                    .shade(factor)
-                    .to_css(false),
-            ),
-            pink: Some(
-                Color::from_hex_str(&self.pink.as_ref().unwrap_or(&self.red))?
-                    .shade(factor)
-                    .to_css(false),
-            ),
+            black: shade("black", &self.black)?,
+            red: shade("red", &self.red)?,
+            green: shade("green", &self.green)?,
+            yellow: shade("yellow", &self.yellow)?,
+            blue: shade("blue", &self.blue)?,
+            magenta: shade("magenta", &self.magenta)?,
+            cyan: shade("cyan", &self.cyan)?,
+            white: shade("white", &self.white)?,
+            orange: Some(match self.orange.as_ref() {
+                Some(v) => shade("orange", v)?,
+                None => shade("yellow", &self.yellow)?,
+            }),
+            pink: Some(match self.pink.as_ref() {
+                Some(v) => shade("pink", v)?,
+                None => shade("red", &self.red)?,
+            }),
         })
     }
 
-    pub fn validation(&self) -> Result<(), String> {
-        for c in self.as_arr() {
+    pub fn validation(&self) -> Result<()> {
+        for (name, c) in TERM_COLOR_NAMES.iter().zip(self.as_arr()) {
             if !c.is_empty() {
-                let _ = Color::from_hex_str(c)?;
+                hex(name, c)?;
             }
         }
 
@@ -165,10 +169,10 @@ impl TermColors {
         ]
     }
 
-    pub fn to_vec_colors(&self) -> Result<Vec<Color>, String> {
+    pub fn to_vec_colors(&self) -> Result<Vec<Color>> {
         let mut colors = Vec::with_capacity(10);
-        for c in self.as_arr() {
-            colors.push(Color::from_hex_str(c)?);
+        for (name, c) in TERM_COLOR_NAMES.iter().zip(self.as_arr()) {
+            colors.push(hex(name, c)?);
         }
         Ok(colors)
     }
@@ -185,10 +189,10 @@ macro_rules! color_enum {
         }
 
         impl $name {
-            pub fn index(&self, i: usize) -> &str {
+            pub fn get(&self, i: usize) -> Option<&str> {
                 match self {
-                    Self::Colors(c) => &c[i],
-                    _ => panic!(concat!(stringify!($name), " not prepared")),
+                    Self::Colors(c) => c.get(i).map(String::as_str),
+                    _ => None,
                 }
             }
 
@@ -210,13 +214,13 @@ color_enum!(Foreground, ForegroundColors, DEFAULT_FOREGROUND_COLOR);
 color_enum!(Selection, SelectionColors, DEFAULT_SELECTION_COLOR);
 
 impl Background {
-    pub fn prepare(&mut self, cfg: &Option<BackgroundShadeConfig>) -> Result<(), String> {
+    pub fn prepare(&mut self, cfg: &Option<BackgroundShadeConfig>) -> Result<()> {
         self.ensure_color();
 
         let s = cfg.unwrap_or(DEFAULT_BACKGROUND_SHADE_CONFIG).0;
         match self {
             Self::Color(c) => {
-                let base = Color::from_hex_str(c)?;
+                let base = hex("", c)?;
 
                 *self = Self::Colors([
                     base.brighten(s[0]).to_css(false),
@@ -232,8 +236,8 @@ impl Background {
                     .enumerate()
                     .find(|(_, v)| !v.is_empty())
                     .map(|(n, v)| (n, v.clone()))
-                    .ok_or("Background base color not defined")?;
-                let find_color = Color::from_hex_str(&find.1)?;
+                    .ok_or_else(|| Error::theme("", None, "base color not defined"))?;
+                let find_color = hex(&format!("[{}]", find.0), &find.1)?;
                 let base_color = if cs[1].is_empty() {
                     let bc = if find.0 == 0 {
                         find_color.brighten(-s[0])
@@ -245,7 +249,7 @@ impl Background {
                     cs[1] = bc.to_css(false);
                     bc
                 } else {
-                    Color::from_hex_str(&cs[1])?
+                    hex("[1]", &cs[1])?
                 };
                 for (n, c) in cs.iter_mut().skip(2).enumerate() {
                     if c.is_empty() {
@@ -258,14 +262,14 @@ impl Background {
         Ok(())
     }
 
-    pub fn validation(&self) -> Result<(), String> {
+    pub fn validation(&self) -> Result<()> {
         match self {
             Self::Color(c) => {
-                let _ = Color::from_hex_str(c)?;
+                hex("", c)?;
             }
             Self::Colors(cs) => {
-                for c in cs {
-                    let _ = Color::from_hex_str(c)?;
+                for (i, c) in cs.iter().enumerate() {
+                    hex(&format!("[{i}]"), c)?;
                 }
             }
             _ => (),
@@ -276,13 +280,13 @@ impl Background {
 }
 
 impl Foreground {
-    pub fn prepare(&mut self, cfg: &Option<ForegroundShadeConfig>) -> Result<(), String> {
+    pub fn prepare(&mut self, cfg: &Option<ForegroundShadeConfig>) -> Result<()> {
         self.ensure_color();
 
         let s = cfg.unwrap_or(DEFAULT_FOREGROUND_SHADE_CONFIG).0;
         match self {
             Self::Color(c) => {
-                let base = Color::from_hex_str(c)?;
+                let base = hex("", c)?;
 
                 *self = Self::Colors([
                     base.brighten(s[0]).to_css(false),
@@ -297,8 +301,8 @@ impl Foreground {
                     .enumerate()
                     .find(|(_, v)| !v.is_empty())
                     .map(|(n, v)| (n, v.clone()))
-                    .ok_or("Foreground base color not defined")?;
-                let find_color = Color::from_hex_str(&find.1)?;
+                    .ok_or_else(|| Error::theme("", None, "base color not defined"))?;
+                let find_color = hex(&format!("[{}]", find.0), &find.1)?;
                 let base_color = if cs[1].is_empty() {
                     let bc = if find.0 == 0 {
                         find_color.brighten(-s[0])
@@ -310,7 +314,7 @@ impl Foreground {
                     cs[1] = bc.to_css(false);
                     bc
                 } else {
-                    Color::from_hex_str(&cs[1])?
+                    hex("[1]", &cs[1])?
                 };
                 for (n, c) in cs.iter_mut().skip(2).enumerate() {
                     if c.is_empty() {
@@ -324,14 +328,14 @@ impl Foreground {
         Ok(())
     }
 
-    pub fn validation(&self) -> Result<(), String> {
+    pub fn validation(&self) -> Result<()> {
         match self {
             Self::Color(c) => {
-                let _ = Color::from_hex_str(c)?;
+                hex("", c)?;
             }
             Self::Colors(cs) => {
-                for c in cs {
-                    let _ = Color::from_hex_str(c)?;
+                for (i, c) in cs.iter().enumerate() {
+                    hex(&format!("[{i}]"), c)?;
                 }
             }
             _ => (),
@@ -342,11 +346,11 @@ impl Foreground {
 }
 
 impl Selection {
-    pub fn prepare(&mut self, cfg: &Option<SelectionShadeConfig>) -> Result<(), String> {
+    pub fn prepare(&mut self, cfg: &Option<SelectionShadeConfig>) -> Result<()> {
         self.ensure_color();
 
         if let Self::Color(c) = self {
-            let base = Color::from_hex_str(c)?;
+            let base = hex("", c)?;
             let s = cfg.unwrap_or(DEFAULT_SELECTION_SHADE_CONFIG).0[0];
 
             *self = Self::Colors([base.to_css(false), base.brighten(s).to_css(false)]);
@@ -354,14 +358,14 @@ impl Selection {
         Ok(())
     }
 
-    pub fn validation(&self) -> Result<(), String> {
+    pub fn validation(&self) -> Result<()> {
         match self {
             Self::Color(c) => {
-                let _ = Color::from_hex_str(c)?;
+                hex("", c)?;
             }
             Self::Colors(cs) => {
-                for c in cs {
-                    let _ = Color::from_hex_str(c)?;
+                for (i, c) in cs.iter().enumerate() {
+                    hex(&format!("[{i}]"), c)?;
                 }
             }
             _ => (),
@@ -379,41 +383,907 @@ pub struct Theme {
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub light: Option<bool>,
 
+    /// Parent theme, merged in when the theme is loaded, see
+    /// [`crate::collection::Collection::get`].
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub extends: Option<String>,
+
+    /// Human readable name, e.g. `Rosé Pine Dawn`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub display_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub author: Option<String>,
+
+    /// Upstream project of the color scheme.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub license: Option<String>,
+
+    /// Free-form tags such as `warm`, `pastel`, `high-contrast`, `retro`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tags: Vec<String>,
+
+    /// Group of related themes (`gruvbox`, `kanagawa`), `variant` names the
+    /// member (`dark`, `wave`).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub family: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub variant: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub colors: Option<ThemeColors>,
 
     // not necessary after preparation
     #[serde(skip_serializing, default)]
     pub config: Option<ThemeConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub syntax: Option<SyntaxColors>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub style: Option<StyleOptions>,
 }
 
 impl Theme {
-    pub fn get_or_insert_colors(&mut self) -> &ThemeColors {
-        self.colors.get_or_insert(Default::default())
+    pub fn is_light(&self) -> bool {
+        self.light.unwrap_or_default()
     }
 
-    // pub fn get_config(&mut self) -> &ThemeConfig {
-    //     self.config.get_or_insert(Default::default())
-    // }
+    pub fn display_name(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .or(self.name.as_deref())
+            .unwrap_or("unknown")
+    }
 
-    pub fn is_light(&mut self) -> bool {
-        *self.light.get_or_insert(Default::default())
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
     }
 
-    pub fn prepare(&mut self) -> Result<(), String> {
+    /// Derives the missing shades, variants and diff colors and validates
+    /// the syntax and style options.
+    /// Errors name the theme and the slot of the offending value.
+    pub fn prepare(mut self) -> Result<PreparedTheme> {
+        let name = self.name.clone();
         let config = self.config.get_or_insert(Default::default());
+        let colors = self.colors.get_or_insert(Default::default());
+        let palette = colors
+            .prepare(config)
+            .and_then(|_| Palette::from_colors(colors))
+            .map_err(|e| e.at("colors").in_theme(name.as_deref()))?;
+        self.check_options()
+            .map_err(|e| e.in_theme(name.as_deref()))?;
+        self.light = Some(self.is_light());
+        let syntax = self
+            .resolve_syntax(&palette)
+            .map_err(|e| e.at("syntax").in_theme(name.as_deref()))?;
+        Ok(PreparedTheme {
+            theme: self,
+            palette,
+            syntax,
+        })
+    }
+
+    pub fn validation(&mut self) -> Result<()> {
+        let name = self.name.clone();
         self.colors
             .get_or_insert(Default::default())
-            .prepare(&config)?;
+            .validation()
+            .map_err(|e| e.at("colors"))
+            .and_then(|_| self.check_options())
+            .map_err(|e| e.in_theme(name.as_deref()))
+    }
+
+    fn check_options(&self) -> Result<()> {
+        if let Some(s) = self.syntax.as_ref() {
+            s.validation().map_err(|e| e.at("syntax"))?;
+        }
+        if let Some(s) = self.style.as_ref() {
+            s.validation().map_err(|e| e.at("style"))?;
+        }
+        Ok(())
+    }
+
+    /// Colors of [`SYNTAX_ROLES`], overridden or defaulted per `light`.
+    fn resolve_syntax(&self, palette: &Palette) -> Result<[Color; SYNTAX_ROLES.len()]> {
+        let mut out = [palette.fg[1]; SYNTAX_ROLES.len()];
+        for (role, c) in SYNTAX_ROLES.iter().zip(out.iter_mut()) {
+            let value = self
+                .syntax
+                .as_ref()
+                .and_then(|s| s.get(role))
+                .unwrap_or_else(|| SyntaxColors::default_ref(role, self.is_light()));
+            *c = if value.starts_with('#') {
+                hex(role, value)?
+            } else {
+                palette
+                    .get(value)
+                    .ok_or_else(|| Error::theme(role, Some(value), "unknown palette reference"))?
+            };
+        }
+        Ok(out)
+    }
+
+    pub fn get_or_insert_style(&mut self) -> &mut StyleOptions {
+        self.style.get_or_insert(Default::default())
+    }
+}
+
+/// A theme returned by [`Theme::prepare`]. Its colors are always present
+/// with every shade, variant and diff color filled in, and parsed once
+/// into a [`Palette`] for the renderers and targets. Derefs to the
+/// underlying [`Theme`].
+#[derive(Debug, Clone)]
+pub struct PreparedTheme {
+    theme: Theme,
+    palette: Palette,
+    syntax: [Color; SYNTAX_ROLES.len()],
+}
+
+impl PreparedTheme {
+    pub fn name(&self) -> &str {
+        self.theme.name.as_deref().unwrap_or("unknown")
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Color of a syntax role, `fg1` for roles outside [`SYNTAX_ROLES`].
+    pub fn syntax_color(&self, role: &str) -> Color {
+        SYNTAX_ROLES
+            .iter()
+            .position(|r| *r == role)
+            .map_or(self.palette.fg[1], |i| self.syntax[i])
+    }
+
+    pub fn style(&self) -> StyleOptions {
+        self.theme.style.clone().unwrap_or_default()
+    }
+
+    /// Whether the default background is light, regardless of `light`.
+    pub fn has_light_background(&self) -> bool {
+        self.palette.bg[1].to_lab().0 > LIGHT_BACKGROUND_LIGHTNESS
+    }
+
+    pub fn into_inner(self) -> Theme {
+        self.theme
+    }
+}
+
+impl std::ops::Deref for PreparedTheme {
+    type Target = Theme;
+
+    fn deref(&self) -> &Theme {
+        &self.theme
+    }
+}
+
+impl Serialize for PreparedTheme {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.theme.serialize(serializer)
+    }
+}
+
+/// The terminal colors of one variant, `orange` and `pink` fall back to
+/// `yellow` and `red`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TermPalette {
+    pub black: Color,
+    pub red: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub blue: Color,
+    pub magenta: Color,
+    pub cyan: Color,
+    pub white: Color,
+    pub orange: Color,
+    pub pink: Color,
+}
+
+impl TermPalette {
+    fn from_colors(t: &TermColors) -> Result<Self> {
+        Ok(Self {
+            black: hex("black", &t.black)?,
+            red: hex("red", &t.red)?,
+            green: hex("green", &t.green)?,
+            yellow: hex("yellow", &t.yellow)?,
+            blue: hex("blue", &t.blue)?,
+            magenta: hex("magenta", &t.magenta)?,
+            cyan: hex("cyan", &t.cyan)?,
+            white: hex("white", &t.white)?,
+            orange: match t.orange.as_ref() {
+                Some(v) => hex("orange", v)?,
+                None => hex("yellow", &t.yellow)?,
+            },
+            pink: match t.pink.as_ref() {
+                Some(v) => hex("pink", v)?,
+                None => hex("red", &t.red)?,
+            },
+        })
+    }
+
+    /// The colors in [`TERM_COLOR_NAMES`] order.
+    pub fn colors(&self) -> [Color; 10] {
+        [
+            self.black,
+            self.red,
+            self.green,
+            self.yellow,
+            self.blue,
+            self.magenta,
+            self.cyan,
+            self.white,
+            self.orange,
+            self.pink,
+        ]
+    }
+
+    pub fn get(&self, name: &str) -> Option<Color> {
+        let i = TERM_COLOR_NAMES.iter().position(|n| *n == name)?;
+        Some(self.colors()[i])
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DiffPalette {
+    pub add: Color,
+    pub delete: Color,
+    pub change: Color,
+    pub text: Color,
+}
+
+/// Every color of a prepared theme, see [`PreparedTheme::palette`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Palette {
+    pub bg: [Color; 5],
+    pub fg: [Color; 4],
+    pub sel: [Color; 2],
+    pub base: TermPalette,
+    pub bright: TermPalette,
+    pub dim: TermPalette,
+    pub diff: DiffPalette,
+    pub comment: Color,
+    pub status_line: Color,
+    pub variable: Color,
+}
+
+impl Palette {
+    /// Parses colors filled in by [`ThemeColors::prepare`].
+    fn from_colors(c: &ThemeColors) -> Result<Self> {
+        let variant = |slot: &str, t: Option<&TermColors>| {
+            let t = t.ok_or_else(|| Error::theme(slot, None, "not set"))?;
+            TermPalette::from_colors(t).map_err(|e| e.at(slot))
+        };
+        let diff = c.diff.as_ref();
+        let diff = |slot: &str, pick: fn(&DiffColors) -> &Option<String>| {
+            filled(slot, diff.and_then(|d| pick(d).as_deref()))
+        };
+        Ok(Self {
+            bg: shades("background", |i| c.background.get(i))?,
+            fg: shades("foreground", |i| c.foreground.get(i))?,
+            sel: shades("selection", |i| c.selection.get(i))?,
+            base: variant("base", Some(&c.base))?,
+            bright: variant("bright", c.bright.as_ref())?,
+            dim: variant("dim", c.dim.as_ref())?,
+            diff: DiffPalette {
+                add: diff("diff.add", |d| &d.add)?,
+                delete: diff("diff.delete", |d| &d.delete)?,
+                change: diff("diff.change", |d| &d.change)?,
+                text: diff("diff.text", |d| &d.text)?,
+            },
+            comment: filled("comment", c.comment.as_deref())?,
+            status_line: filled("status_line", c.status_line.as_deref())?,
+            variable: filled("variable", c.variable.as_deref())?,
+        })
+    }
+
+    /// Resolves a palette reference such as `orange.bright`, `bg2` or
+    /// `diff.add`.
+    pub fn get(&self, r: &str) -> Option<Color> {
+        let idx = |s: &str| s.parse::<usize>().ok();
+        match r.split_once('.') {
+            Some(("diff", k)) => match k {
+                "add" => Some(self.diff.add),
+                "delete" => Some(self.diff.delete),
+                "change" => Some(self.diff.change),
+                "text" => Some(self.diff.text),
+                _ => None,
+            },
+            Some((name, "base")) => self.base.get(name),
+            Some((name, "bright")) => self.bright.get(name),
+            Some((name, "dim")) => self.dim.get(name),
+            Some(_) => None,
+            None => match r {
+                "comment" => Some(self.comment),
+                "status_line" => Some(self.status_line),
+                "variable" => Some(self.variable),
+                _ => {
+                    if let Some(i) = r.strip_prefix("bg").and_then(idx) {
+                        self.bg.get(i).copied()
+                    } else if let Some(i) = r.strip_prefix("fg").and_then(idx) {
+                        self.fg.get(i).copied()
+                    } else {
+                        r.strip_prefix("sel")
+                            .and_then(idx)
+                            .and_then(|i| self.sel.get(i).copied())
+                    }
+                }
+            },
+        }
+    }
+
+    /// Every slot as `(reference, color)`: terminal colors by variant,
+    /// then the shades, comment/variable/status_line and `diff.*`.
+    pub fn slots(&self) -> Vec<(String, Color)> {
+        TERM_VARIANTS
+            .iter()
+            .flat_map(|v| TERM_COLOR_NAMES.iter().map(move |n| format!("{n}.{v}")))
+            .chain(PALETTE_SLOTS.iter().map(|s| s.to_string()))
+            .chain(DIFF_SLOTS.iter().map(|s| s.to_string()))
+            .filter_map(|r| {
+                let c = self.get(&r)?;
+                Some((r, c))
+            })
+            .collect()
+    }
+}
+
+fn shades<'a, const N: usize>(
+    slot: &str,
+    get: impl Fn(usize) -> Option<&'a str>,
+) -> Result<[Color; N]> {
+    let mut out = [Color::new(0.0, 0.0, 0.0, 1.0); N];
+    for (i, c) in out.iter_mut().enumerate() {
+        *c = filled(&format!("{slot}[{i}]"), get(i))?;
+    }
+    Ok(out)
+}
+
+/// Syntax role overrides. Each value is either a hex color (`#rrggbb`)
+/// or a palette reference such as `orange.bright`, `fg2` or `comment`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyntaxColors {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub bracket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub builtin0: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub builtin1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub builtin2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub builtin3: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub conditional: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub r#const: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub dep: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub func: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ident: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub keyword: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub operator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub preproc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub regex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub statement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub string: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub r#type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub variable: Option<String>,
+}
+
+pub const SYNTAX_ROLES: [&str; 21] = [
+    "bracket",
+    "builtin0",
+    "builtin1",
+    "builtin2",
+    "builtin3",
+    "comment",
+    "conditional",
+    "const",
+    "dep",
+    "field",
+    "func",
+    "ident",
+    "keyword",
+    "number",
+    "operator",
+    "preproc",
+    "regex",
+    "statement",
+    "string",
+    "type",
+    "variable",
+];
+
+impl SyntaxColors {
+    pub fn get(&self, role: &str) -> Option<&str> {
+        match role {
+            "bracket" => self.bracket.as_deref(),
+            "builtin0" => self.builtin0.as_deref(),
+            "builtin1" => self.builtin1.as_deref(),
+            "builtin2" => self.builtin2.as_deref(),
+            "builtin3" => self.builtin3.as_deref(),
+            "comment" => self.comment.as_deref(),
+            "conditional" => self.conditional.as_deref(),
+            "const" => self.r#const.as_deref(),
+            "dep" => self.dep.as_deref(),
+            "field" => self.field.as_deref(),
+            "func" => self.func.as_deref(),
+            "ident" => self.ident.as_deref(),
+            "keyword" => self.keyword.as_deref(),
+            "number" => self.number.as_deref(),
+            "operator" => self.operator.as_deref(),
+            "preproc" => self.preproc.as_deref(),
+            "regex" => self.regex.as_deref(),
+            "statement" => self.statement.as_deref(),
+            "string" => self.string.as_deref(),
+            "type" => self.r#type.as_deref(),
+            "variable" => self.variable.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Palette reference used for `role` when the theme does not override it.
+    pub fn default_ref(role: &str, is_light: bool) -> &'static str {
+        let shade = |light: &'static str, dark: &'static str| if is_light { light } else { dark };
+        match role {
+            "bracket" => "fg2",
+            "builtin0" => "red.base",
+            "builtin1" => shade("cyan.dim", "cyan.bright"),
+            "builtin2" => shade("orange.dim", "orange.bright"),
+            "builtin3" => shade("red.dim", "red.bright"),
+            "comment" => "comment",
+            "conditional" => shade("magenta.dim", "magenta.bright"),
+            "const" => shade("orange.dim", "orange.bright"),
+            "dep" => "fg3",
+            "field" => "blue.base",
+            "func" => shade("blue.dim", "blue.bright"),
+            "ident" => "cyan.base",
+            "keyword" => "magenta.base",
+            "number" => "orange.base",
+            "operator" => "fg2",
+            "preproc" => shade("pink.dim", "pink.bright"),
+            "regex" => shade("yellow.dim", "yellow.bright"),
+            "statement" => "magenta.base",
+            "string" => "green.base",
+            "type" => "yellow.base",
+            "variable" => "variable",
+            _ => "fg1",
+        }
+    }
+
+    pub fn is_palette_ref(s: &str) -> bool {
+        match s.split_once('.') {
+            Some(("diff", k)) => matches!(k, "add" | "delete" | "change" | "text"),
+            Some((c, k)) => TERM_COLOR_NAMES.contains(&c) && TERM_VARIANTS.contains(&k),
+            None => PALETTE_SLOTS.contains(&s),
+        }
+    }
+
+    pub fn validation(&self) -> Result<()> {
+        for role in SYNTAX_ROLES {
+            if let Some(v) = self.get(role) {
+                if v.starts_with('#') {
+                    hex(role, v)?;
+                } else if !Self::is_palette_ref(v) {
+                    return Err(Error::theme(role, Some(v), "unknown palette reference"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const DEFAULT_TRANSPARENT_OPACITY: f32 = 0.9;
+
+const STYLE_TOKENS: [&str; 7] = [
+    "bold",
+    "italic",
+    "underline",
+    "undercurl",
+    "strikethrough",
+    "reverse",
+    "NONE",
+];
+
+/// Editor style options. Every field is optional so that theme, user config
+/// and command line values can be layered with [`StyleOptions::merge`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StyleOptions {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub transparent: Option<bool>,
+
+    /// Alacritty `window.opacity` used when `transparent` is set.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub opacity: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub dim_inactive: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub inverse: Option<InverseOptions>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub styles: Option<StyleCategories>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InverseOptions {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub match_paren: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub visual: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub search: Option<bool>,
+}
+
+/// Comma separated highlight attributes per syntax category,
+/// e.g. `comments = "italic"` or `keywords = "bold,italic"`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StyleCategories {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub conditionals: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub constants: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub functions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub keywords: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub numbers: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub operators: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub preprocs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub strings: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub types: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub variables: Option<String>,
+}
+
+pub const STYLE_CATEGORIES: [&str; 11] = [
+    "comments",
+    "conditionals",
+    "constants",
+    "functions",
+    "keywords",
+    "numbers",
+    "operators",
+    "preprocs",
+    "strings",
+    "types",
+    "variables",
+];
+
+impl StyleCategories {
+    pub fn get(&self, category: &str) -> Option<&str> {
+        match category {
+            "comments" => self.comments.as_deref(),
+            "conditionals" => self.conditionals.as_deref(),
+            "constants" => self.constants.as_deref(),
+            "functions" => self.functions.as_deref(),
+            "keywords" => self.keywords.as_deref(),
+            "numbers" => self.numbers.as_deref(),
+            "operators" => self.operators.as_deref(),
+            "preprocs" => self.preprocs.as_deref(),
+            "strings" => self.strings.as_deref(),
+            "types" => self.types.as_deref(),
+            "variables" => self.variables.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, category: &str) -> Option<&mut Option<String>> {
+        match category {
+            "comments" => Some(&mut self.comments),
+            "conditionals" => Some(&mut self.conditionals),
+            "constants" => Some(&mut self.constants),
+            "functions" => Some(&mut self.functions),
+            "keywords" => Some(&mut self.keywords),
+            "numbers" => Some(&mut self.numbers),
+            "operators" => Some(&mut self.operators),
+            "preprocs" => Some(&mut self.preprocs),
+            "strings" => Some(&mut self.strings),
+            "types" => Some(&mut self.types),
+            "variables" => Some(&mut self.variables),
+            _ => None,
+        }
+    }
+
+    /// Adds `attr` to the category keeping the attributes already set.
+    pub fn add(&mut self, category: &str, attr: &str) -> Result<()> {
+        let slot = self
+            .get_mut(category)
+            .ok_or_else(|| Error::Usage(format!("Unknown style category '{category}'")))?;
+        match slot {
+            Some(v) if v != "NONE" && !v.split(',').any(|t| t.trim() == attr) => {
+                v.push(',');
+                v.push_str(attr);
+            }
+            Some(v) if v != "NONE" => {}
+            _ => *slot = Some(attr.into()),
+        }
+        Ok(())
+    }
+}
+
+impl StyleOptions {
+    /// Overrides fields of `self` with the fields set in `other`.
+    pub fn merge(&mut self, other: &StyleOptions) {
+        if other.transparent.is_some() {
+            self.transparent = other.transparent;
+        }
+        if other.opacity.is_some() {
+            self.opacity = other.opacity;
+        }
+        if other.dim_inactive.is_some() {
+            self.dim_inactive = other.dim_inactive;
+        }
+        if let Some(o) = other.inverse.as_ref() {
+            let inv = self.inverse.get_or_insert(Default::default());
+            if o.match_paren.is_some() {
+                inv.match_paren = o.match_paren;
+            }
+            if o.visual.is_some() {
+                inv.visual = o.visual;
+            }
+            if o.search.is_some() {
+                inv.search = o.search;
+            }
+        }
+        if let Some(o) = other.styles.as_ref() {
+            let styles = self.styles.get_or_insert(Default::default());
+            for category in STYLE_CATEGORIES {
+                if let Some(v) = o.get(category) {
+                    *styles.get_mut(category).unwrap() = Some(v.into());
+                }
+            }
+        }
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.transparent.unwrap_or(false)
+    }
+
+    pub fn is_dim_inactive(&self) -> bool {
+        self.dim_inactive.unwrap_or(false)
+    }
+
+    pub fn inverse(&self, target: &str) -> bool {
+        let Some(inv) = self.inverse.as_ref() else {
+            return false;
+        };
+        match target {
+            "match_paren" => inv.match_paren,
+            "visual" => inv.visual,
+            "search" => inv.search,
+            _ => None,
+        }
+        .unwrap_or(false)
+    }
+
+    pub fn set_inverse(&mut self, target: &str) -> Result<()> {
+        let inv = self.inverse.get_or_insert(Default::default());
+        match target {
+            "match_paren" => inv.match_paren = Some(true),
+            "visual" => inv.visual = Some(true),
+            "search" => inv.search = Some(true),
+            _ => return Err(Error::Usage(format!("Unknown inverse target '{target}'"))),
+        }
         Ok(())
     }
 
-    pub fn validation(&mut self) -> Result<(), String> {
-        self.colors.get_or_insert(Default::default()).validation()?;
+    pub fn style(&self, category: &str) -> &str {
+        self.styles
+            .as_ref()
+            .and_then(|s| s.get(category))
+            .unwrap_or("NONE")
+    }
+
+    /// Alacritty window opacity, `None` unless `transparent` is on.
+    pub fn window_opacity(&self) -> Option<f32> {
+        self.is_transparent().then(|| {
+            self.opacity
+                .unwrap_or(DEFAULT_TRANSPARENT_OPACITY)
+                .clamp(0.0, 1.0)
+        })
+    }
+
+    pub fn validation(&self) -> Result<()> {
+        if let Some(styles) = self.styles.as_ref() {
+            for category in STYLE_CATEGORIES {
+                let Some(v) = styles.get(category) else {
+                    continue;
+                };
+                for token in v.split(',').map(str::trim) {
+                    if !STYLE_TOKENS.contains(&token) {
+                        return Err(Error::theme(
+                            &format!("styles.{category}"),
+                            Some(v),
+                            format!("unknown style '{token}'"),
+                        ));
+                    }
+                }
+            }
+        }
         Ok(())
     }
 }
 
+const DEFAULT_FAVORITE_WEIGHT: f64 = 3.0;
+const DEFAULT_AVOID_RECENT: usize = 3;
+
+/// Random theme selection preferences.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RandomOptions {
+    /// Themes picked more often, see `favorite_weight`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub favorites: Vec<String>,
+
+    /// Themes never picked at random.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub blocklist: Vec<String>,
+
+    /// Weight of a favorite relative to a regular theme (1.0).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub favorite_weight: Option<f64>,
+
+    /// Do not repeat any of the last N applied themes.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub avoid_recent: Option<usize>,
+}
+
+impl RandomOptions {
+    pub fn weight(&self, name: &str) -> f64 {
+        if self.favorites.iter().any(|f| f == name) {
+            self.favorite_weight
+                .unwrap_or(DEFAULT_FAVORITE_WEIGHT)
+                .max(0.0)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_blocked(&self, name: &str) -> bool {
+        self.blocklist.iter().any(|b| b == name)
+    }
+
+    pub fn avoid_recent(&self) -> usize {
+        self.avoid_recent.unwrap_or(DEFAULT_AVOID_RECENT)
+    }
+}
+
+/// Config files of the targets, a leading `~/` stands for the home
+/// directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PathsConfig {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub nvim: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub lualine: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub alacritty: Option<PathBuf>,
+
+    /// Directory searched for `tvibe-target-*` plugins before `PATH`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub plugins: Option<PathBuf>,
+}
+
+/// Alacritty font set along with every applied theme, the font flags
+/// override single fields.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FontConfig {
+    /// Nerd Font family, matched like `--font`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub family: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub size: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub style: Option<String>,
+}
+
+impl FontConfig {
+    pub fn is_empty(&self) -> bool {
+        self.family.is_none() && self.size.is_none() && self.style.is_none()
+    }
+
+    /// Checks a font size in points, it must be a positive number.
+    pub fn check_size(size: f32) -> Result<f32> {
+        if size.is_finite() && size > 0.0 {
+            Ok(size)
+        } else {
+            Err(Error::Usage(format!(
+                "font size must be a positive number, got {size}"
+            )))
+        }
+    }
+}
+
+const DEFAULT_HOOK_TIMEOUT: u64 = 30;
+
+/// Shell commands run around applying a theme by the command line.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Run before anything is written, a failure cancels the apply.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub pre_apply: Vec<String>,
+
+    /// Run after the theme is written, e.g. `swaymsg reload`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub post_apply: Vec<String>,
+
+    /// Run after one target is written, keyed by target name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub targets: BTreeMap<String, Vec<String>>,
+
+    /// Seconds a command or plugin may run before it is killed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timeout: Option<u64>,
+}
+
+impl HooksConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_HOOK_TIMEOUT))
+    }
+
+    pub fn for_target(&self, target: &str) -> &[String] {
+        self.targets.get(target).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// User configuration, `$XDG_CONFIG_HOME/tvibe/config.toml` or the file
+/// named by `TVIBE_CONFIG`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub paths: Option<PathsConfig>,
+
+    /// Targets written when a theme is applied, all of them by default.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub targets: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub font: Option<FontConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub style: Option<StyleOptions>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub random: Option<RandomOptions>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub hooks: Option<HooksConfig>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
     #[serde(skip_serializing_if = "Option::is_none", default)]
@@ -448,6 +1318,32 @@ impl Default for ThemeConfig {
     }
 }
 
+pub const TERM_COLOR_NAMES: [&str; 10] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "orange", "pink",
+];
+
+pub const TERM_VARIANTS: [&str; 3] = ["base", "bright", "dim"];
+
+/// Palette references outside the terminal colors and `diff.*`.
+pub const PALETTE_SLOTS: [&str; 14] = [
+    "bg0",
+    "bg1",
+    "bg2",
+    "bg3",
+    "bg4",
+    "fg0",
+    "fg1",
+    "fg2",
+    "fg3",
+    "sel0",
+    "sel1",
+    "comment",
+    "variable",
+    "status_line",
+];
+
+pub const DIFF_SLOTS: [&str; 4] = ["diff.add", "diff.delete", "diff.change", "diff.text"];
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ThemeColors {
     #[serde(default)]
@@ -482,41 +1378,51 @@ pub struct ThemeColors {
 }
 
 impl ThemeColors {
-    pub fn prepare(&mut self, config: &ThemeConfig) -> Result<(), String> {
-        self.background.prepare(&config.background_shade)?;
-        self.foreground.prepare(&config.foreground_shade)?;
-        self.selection.prepare(&config.selection_shade)?;
+    pub fn prepare(&mut self, config: &ThemeConfig) -> Result<()> {
+        self.background
+            .prepare(&config.background_shade)
+            .map_err(|e| e.at("background"))?;
+        self.foreground
+            .prepare(&config.foreground_shade)
+            .map_err(|e| e.at("foreground"))?;
+        self.selection
+            .prepare(&config.selection_shade)
+            .map_err(|e| e.at("selection"))?;
 
         let shade = config.shade_factor.unwrap_or(DEFAULT_SHADE_FACTOR);
 
-        self.bright.get_or_insert(self.base.shade(Some(shade))?);
-        self.dim.get_or_insert(self.base.shade(Some(-shade))?);
+        let base = |e: Error| e.at("base");
+        self.bright
+            .get_or_insert(self.base.shade(Some(shade)).map_err(base)?);
+        self.dim
+            .get_or_insert(self.base.shade(Some(-shade)).map_err(base)?);
 
-        let bg = Color::from_hex_str(self.background.index(1))?;
+        let bg1 = filled("background[1]", self.background.get(1))?;
+        let fg1 = filled("foreground[1]", self.foreground.get(1))?;
         let diff_cfg = config.diff_blend.unwrap_or_default();
 
         let diff = self.diff.get_or_insert_with(DiffColors::default);
 
         diff.add.get_or_insert(
-            bg.blend(&Color::from_hex_str(&self.base.green)?, diff_cfg.add)
+            bg1.blend(&hex("base.green", &self.base.green)?, diff_cfg.add)
                 .to_css(false),
         );
         diff.delete.get_or_insert(
-            bg.blend(&Color::from_hex_str(&self.base.red)?, diff_cfg.delete)
+            bg1.blend(&hex("base.red", &self.base.red)?, diff_cfg.delete)
                 .to_css(false),
         );
         diff.change.get_or_insert(
-            bg.blend(&Color::from_hex_str(&self.base.blue)?, diff_cfg.change)
+            bg1.blend(&hex("base.blue", &self.base.blue)?, diff_cfg.change)
                 .to_css(false),
         );
         diff.text.get_or_insert(
-            bg.blend(&Color::from_hex_str(&self.base.cyan)?, diff_cfg.text)
+            bg1.blend(&hex("base.cyan", &self.base.cyan)?, diff_cfg.text)
                 .to_css(false),
         );
 
         self.comment.get_or_insert(
-            bg.blend(
-                &Color::from_hex_str(self.foreground.index(1))?,
+            bg1.blend(
+                &fg1,
                 config
                     .comment_blend_factor
                     .unwrap_or(DEFAULT_COMMENT_BLEND_FACTOR),
@@ -524,33 +1430,40 @@ impl ThemeColors {
             .to_css(false),
         );
 
-        self.variable.get_or_insert(self.foreground.index(1).into());
+        if self.variable.is_none() {
+            self.variable = self.foreground.get(1).map(String::from);
+        }
 
-        self.status_line
-            .get_or_insert(self.background.index(0).into());
+        if self.status_line.is_none() {
+            self.status_line = self.background.get(0).map(String::from);
+        }
 
         Ok(())
     }
 
-    pub fn validation(&self) -> Result<(), String> {
-        self.base.validation()?;
+    pub fn validation(&self) -> Result<()> {
+        self.base.validation().map_err(|e| e.at("base"))?;
         if let Some(b) = self.bright.as_ref() {
-            b.validation()?;
+            b.validation().map_err(|e| e.at("bright"))?;
         }
         if let Some(d) = self.dim.as_ref() {
-            d.validation()?;
+            d.validation().map_err(|e| e.at("dim"))?;
         }
         if let Some(d) = self.diff.as_ref() {
-            d.validation()?;
+            d.validation().map_err(|e| e.at("diff"))?;
         }
-        self.background.validation()?;
-        self.foreground.validation()?;
-        self.selection.validation()?;
+        self.background
+            .validation()
+            .map_err(|e| e.at("background"))?;
+        self.foreground
+            .validation()
+            .map_err(|e| e.at("foreground"))?;
+        self.selection.validation().map_err(|e| e.at("selection"))?;
         if let Some(c) = self.comment.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("comment", c)?;
         }
         if let Some(c) = self.status_line.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("status_line", c)?;
         }
 
         Ok(())
@@ -570,92 +1483,178 @@ pub struct DiffColors {
 }
 
 impl DiffColors {
-    pub fn validation(&self) -> Result<(), String> {
+    pub fn validation(&self) -> Result<()> {
         if let Some(c) = self.add.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("add", c)?;
         }
         if let Some(c) = self.delete.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("delete", c)?;
         }
         if let Some(c) = self.change.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("change", c)?;
         }
         if let Some(c) = self.text.as_ref() {
-            let _ = Color::from_hex_str(c)?;
+            hex("text", c)?;
         }
 
         Ok(())
     }
 }
 
-#[macro_export]
-macro_rules! term_colors {
-    (
-        $black:expr, $red:expr, $green:expr, $yellow:expr,
-        $blue:expr, $magenta:expr, $cyan:expr, $white:expr,
-        $orange:expr, $pink:expr $(,)?
-    ) => {{
-        TermColors {
-            black: $black.into(),
-            red: $red.into(),
-            green: $green.into(),
-            yellow: $yellow.into(),
-            blue: $blue.into(),
-            magenta: $magenta.into(),
-            cyan: $cyan.into(),
-            white: $white.into(),
-            orange: if $orange.is_empty() { None } else { Some($orange.into()) },
-            pink: if $pink.is_empty() { None } else { Some($pink.into()) },
-        }
-    }};
-}
-
-#[macro_export]
-macro_rules! bg_colors {
-    ( [ $($color:expr),* $(,)? ] ) => {
-        Background::Colors([ $( $color.into() ),* ])
-    };
-}
-
-#[macro_export]
-macro_rules! fg_colors {
-    ( [ $($color:expr),* $(,)? ] ) => {
-        Foreground::Colors([ $( $color.into() ),* ])
-    };
-}
-
-#[macro_export]
-macro_rules! sel_colors {
-    ( [ $($color:expr),* $(,)? ] ) => {
-        Selection::Colors([ $( $color.into() ),* ])
-    };
-}
-
 pub mod alacritty {
     use super::*;
 
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    use toml_edit::{DocumentMut, Item, Table, TableLike};
+
+    /// Alacritty config document. Edits touch only the keys tvibe owns,
+    /// comments and formatting of the rest of the file are preserved.
+    #[derive(Debug, Clone, Default)]
     pub struct Config {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        pub colors: Option<Colors>,
+        doc: DocumentMut,
+    }
 
-        #[serde(skip_serializing_if = "Option::is_none")]
-        pub font: Option<Font>,
+    impl std::str::FromStr for Config {
+        type Err = toml_edit::TomlError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self { doc: s.parse()? })
+        }
+    }
 
-        #[serde(flatten)]
-        pub other: toml::Value,
+    impl std::fmt::Display for Config {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.doc)
+        }
     }
 
     impl Config {
-        pub fn replace_colors_from_theme(&mut self, theme: &ThemeColors) {
-            let new_colors = Colors::from_theme(theme);
-            self.colors = Some(new_colors);
+        pub fn replace_colors_from_theme(&mut self, palette: &Palette) -> Result<()> {
+            let new_colors = toml::to_string(&Colors::from_palette(palette))
+                .map_err(Error::config)?
+                .parse::<DocumentMut>()
+                .map_err(Error::config)?;
+            merge_table(
+                table_mut(self.doc.as_table_mut(), "colors"),
+                new_colors.as_table().clone(),
+            );
+            Ok(())
+        }
+
+        pub fn set_window_opacity(&mut self, opacity: f32) {
+            let opacity = (opacity as f64 * 100.0).round() / 100.0;
+            self.set_value(&["window", "opacity"], opacity.into());
         }
 
         pub fn set_font_family(&mut self, f: &str) {
-            self.font.replace(Font {
-               normal: FontInner { family: f.into() } 
-            });
+            self.set_value(&["font", "normal", "family"], f.into());
+        }
+
+        /// Current `[font]` section, missing keys stay `None`.
+        pub fn font(&self) -> Result<Font> {
+            #[derive(Deserialize)]
+            struct Doc {
+                #[serde(default)]
+                font: Font,
+            }
+            toml::from_str::<Doc>(&self.doc.to_string())
+                .map(|d| d.font)
+                .map_err(Error::config)
+        }
+
+        /// Merges `font` into the `[font]` section, only the fields that are
+        /// set are written.
+        pub fn set_font(&mut self, font: &Font) -> Result<()> {
+            let new_font = toml::to_string(font)
+                .map_err(Error::config)?
+                .parse::<DocumentMut>()
+                .map_err(Error::config)?;
+            merge_table(
+                table_mut(self.doc.as_table_mut(), "font"),
+                new_font.as_table().clone(),
+            );
+            Ok(())
+        }
+
+        pub fn font_family(&self) -> Option<&str> {
+            self.doc.get("font")?.get("normal")?.get("family")?.as_str()
+        }
+
+        fn set_value(&mut self, path: &[&str], value: toml_edit::Value) {
+            let (key, tables) = path.split_last().expect("empty key path");
+            let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+            for name in tables {
+                table = table_mut(table, name);
+            }
+            set_value(table, key, value);
+        }
+    }
+
+    /// Returns the sub-table `name`, creating an implicit one when missing.
+    fn table_mut<'a>(parent: &'a mut dyn TableLike, name: &str) -> &'a mut dyn TableLike {
+        if !parent.get(name).is_some_and(Item::is_table_like) {
+            let mut t = Table::new();
+            t.set_implicit(true);
+            parent.insert(name, Item::Table(t));
+        }
+        parent
+            .get_mut(name)
+            .and_then(Item::as_table_like_mut)
+            .expect("table was just inserted")
+    }
+
+    /// Replaces a value keeping its surrounding whitespace and trailing comment.
+    fn set_value(table: &mut dyn TableLike, key: &str, mut value: toml_edit::Value) {
+        if let Some(Item::Value(old)) = table.get_mut(key) {
+            *value.decor_mut() = old.decor().clone();
+            *old = value;
+            return;
+        }
+        table.insert(key, Item::Value(value));
+    }
+
+    fn merge_table(dst: &mut dyn TableLike, src: Table) {
+        for (key, item) in src {
+            if let Some(existing) = dst.get_mut(&key) {
+                match item {
+                    Item::Table(t) if existing.is_table_like() => {
+                        merge_table(existing.as_table_like_mut().unwrap(), t);
+                        continue;
+                    }
+                    Item::Value(v) if existing.is_value() => {
+                        set_value(dst, &key, v);
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            let mut item = item;
+            clear_positions(&mut item);
+            dst.insert(&key, item);
+        }
+    }
+
+    /// New tables without a position are rendered right after their
+    /// neighbours instead of at the positions of the source document.
+    fn clear_positions(item: &mut Item) {
+        match item {
+            Item::Table(t) => {
+                t.set_position(None);
+                if t.decor().prefix().and_then(|p| p.as_str()) == Some("") {
+                    t.decor_mut().set_prefix("\n");
+                }
+                for (_, child) in t.iter_mut() {
+                    clear_positions(child);
+                }
+            }
+            Item::ArrayOfTables(a) => {
+                for t in a.iter_mut() {
+                    t.set_position(None);
+                    for (_, child) in t.iter_mut() {
+                        clear_positions(child);
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
@@ -687,41 +1686,37 @@ pub mod alacritty {
     }
 
     impl Colors {
-        pub fn from_theme(theme: &ThemeColors) -> Self {
+        pub fn from_palette(p: &Palette) -> Self {
             Self {
                 primary: Some(PrimaryColors {
-                    background: Some(theme.background.index(1).into()),
-                    foreground: Some(theme.foreground.index(1).into()),
-                    dim_foreground: Some(theme.foreground.index(2).into()),
-                    bright_foreground: Some(theme.foreground.index(0).into()),
+                    background: Some(p.bg[1].to_string()),
+                    foreground: Some(p.fg[1].to_string()),
+                    dim_foreground: Some(p.fg[2].to_string()),
+                    bright_foreground: Some(p.fg[0].to_string()),
                 }),
                 cursor: Some(CursorColors {
-                    text: Some(theme.background.index(1).into()),
-                    cursor: Some(theme.foreground.index(2).into()),
+                    text: Some(p.bg[1].to_string()),
+                    cursor: Some(p.fg[2].to_string()),
                 }),
                 // vi_mode_cursor: Some(CursorColors {
-                //     text: Some(theme.background.index(1).into()),
-                //     cursor: Some(theme.foreground.index(3).into()),
+                //     text: Some(p.bg[1].to_string()),
+                //     cursor: Some(p.fg[3].to_string()),
                 // }),
                 selection: Some(SelectionColors {
-                    text: Some(theme.foreground.index(1).into()),
-                    background: Some(theme.selection.index(0).into()),
+                    text: Some(p.fg[1].to_string()),
+                    background: Some(p.sel[0].to_string()),
                 }),
-                normal: Some(AnsiColors::from(&theme.base)),
-                bright: theme.bright.as_ref().map(AnsiColors::from),
-                dim: theme.dim.as_ref().map(AnsiColors::from),
+                normal: Some(AnsiColors::from(&p.base)),
+                bright: Some(AnsiColors::from(&p.bright)),
+                dim: Some(AnsiColors::from(&p.dim)),
                 indexed_colors: vec![
                     IndexedColor {
                         index: 16,
-                        color: theme
-                            .base
-                            .orange
-                            .clone()
-                            .unwrap_or(theme.base.yellow.clone()),
+                        color: p.base.orange.to_string(),
                     },
                     IndexedColor {
                         index: 17,
-                        color: theme.base.pink.clone().unwrap_or(theme.base.red.clone()),
+                        color: p.base.pink.to_string(),
                     },
                 ],
                 ..Default::default()
@@ -729,17 +1724,17 @@ pub mod alacritty {
         }
     }
 
-    impl From<&TermColors> for AnsiColors {
-        fn from(t: &TermColors) -> Self {
+    impl From<&TermPalette> for AnsiColors {
+        fn from(t: &TermPalette) -> Self {
             Self {
-                black: t.black.clone(),
-                red: t.red.clone(),
-                green: t.green.clone(),
-                yellow: t.yellow.clone(),
-                blue: t.blue.clone(),
-                magenta: t.magenta.clone(),
-                cyan: t.cyan.clone(),
-                white: t.white.clone(),
+                black: t.black.to_string(),
+                red: t.red.to_string(),
+                green: t.green.to_string(),
+                yellow: t.yellow.to_string(),
+                blue: t.blue.to_string(),
+                magenta: t.magenta.to_string(),
+                cyan: t.cyan.to_string(),
+                white: t.white.to_string(),
             }
         }
     }
@@ -814,13 +1809,224 @@ pub mod alacritty {
         pub color: String,
     }
 
+    /// `[font]` section, every field is optional so a partial value merged
+    /// with `Config::set_font` leaves the rest of the user's settings alone.
     #[derive(Debug, Clone, Default, Serialize, Deserialize)]
     pub struct Font {
-        pub normal: FontInner,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub normal: Option<FontDescription>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bold: Option<FontDescription>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub italic: Option<FontDescription>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bold_italic: Option<FontDescription>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub size: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub offset: Option<FontDelta>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub glyph_offset: Option<FontDelta>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub builtin_box_drawing: Option<bool>,
     }
 
     #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-    pub struct FontInner {
-        pub family: String,
+    pub struct FontDescription {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub family: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub style: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+    pub struct FontDelta {
+        #[serde(default)]
+        pub x: i32,
+        #[serde(default)]
+        pub y: i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEFAULT_TRANSPARENT_OPACITY, FontConfig, Palette, StyleOptions, Theme, ThemeColors,
+        ThemeConfig,
+        alacritty::{Config, Font},
+    };
+    use crate::{collection, color::Color, error::Error};
+
+    const THEME: &str = r##"name = "plain"
+
+[colors.base]
+black = "#1c2023"
+red = "#bf616a"
+green = "#a3be8c"
+yellow = "#ebcb8b"
+blue = "#81a1c1"
+magenta = "#b48ead"
+cyan = "#88c0d0"
+white = "#e5e9f0"
+"##;
+
+    fn theme(extra: &str) -> Theme {
+        toml::from_str(&format!("{THEME}{extra}")).unwrap()
+    }
+
+    /// Slot of a theme error, `None` for other errors.
+    fn slot(e: Error) -> Option<String> {
+        match e {
+            Error::Theme { slot, .. } => Some(slot),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn palette_parses_prepared_colors() {
+        let theme = theme("").prepare().unwrap();
+        let palette = theme.palette();
+        let hex = |c: Color| c.to_css(false);
+        assert_eq!(hex(palette.base.red), "#bf616a");
+        assert_eq!(hex(palette.base.cyan), "#88c0d0");
+        // orange and pink fall back to yellow and red
+        assert_eq!(hex(palette.base.orange), "#ebcb8b");
+        assert_eq!(hex(palette.base.pink), "#bf616a");
+        assert_ne!(hex(palette.bright.red), "#bf616a");
+        assert_ne!(hex(palette.dim.red), "#bf616a");
+    }
+
+    #[test]
+    fn palette_resolves_references_and_syntax_roles() {
+        let theme = theme("\n[syntax]\nkeyword = \"pink.bright\"\nstring = \"#010203\"\n")
+            .prepare()
+            .unwrap();
+        let p = theme.palette();
+        let hex = |c: Option<Color>| c.map(|c| c.to_css(false));
+        assert_eq!(hex(p.get("red.base")), Some("#bf616a".into()));
+        assert_eq!(hex(p.get("bg2")), hex(Some(p.bg[2])));
+        assert_eq!(hex(p.get("diff.text")), hex(Some(p.diff.text)));
+        assert_eq!(hex(p.get("bg5")), None);
+        assert_eq!(hex(p.get("red.pale")), None);
+        assert_eq!(p.slots().len(), 48);
+
+        let role = |r: &str| hex(Some(theme.syntax_color(r)));
+        assert_eq!(role("keyword"), hex(Some(p.bright.pink)));
+        assert_eq!(role("string"), Some("#010203".into()));
+        assert_eq!(role("type"), hex(Some(p.base.yellow)));
+        assert_eq!(role("no_such_role"), hex(Some(p.fg[1])));
+    }
+
+    #[test]
+    fn palette_reports_unprepared_slots() {
+        let mut colors = ThemeColors::default();
+        assert_eq!(
+            slot(Palette::from_colors(&colors).unwrap_err()).as_deref(),
+            Some("background[0]")
+        );
+
+        colors = theme("").colors.unwrap();
+        colors.prepare(&ThemeConfig::default()).unwrap();
+        Palette::from_colors(&colors).unwrap();
+
+        let mut no_bright = colors.clone();
+        no_bright.bright = None;
+        assert_eq!(
+            slot(Palette::from_colors(&no_bright).unwrap_err()).as_deref(),
+            Some("bright")
+        );
+        let mut no_diff = colors.clone();
+        no_diff.diff = None;
+        assert_eq!(
+            slot(Palette::from_colors(&no_diff).unwrap_err()).as_deref(),
+            Some("diff.add")
+        );
+        let mut bad_dim = colors;
+        bad_dim.dim.as_mut().unwrap().green = "#12345".into();
+        assert_eq!(
+            slot(Palette::from_colors(&bad_dim).unwrap_err()).as_deref(),
+            Some("dim.green")
+        );
+    }
+
+    #[test]
+    fn invalid_color_fails_prepare() {
+        let mut theme = theme("");
+        theme.colors.as_mut().unwrap().base.red = "nope".into();
+        let err = theme.prepare().unwrap_err();
+        assert_eq!(err.exit_code(), 65);
+        assert!(slot(err).unwrap().ends_with("red"));
+    }
+
+    const ALACRITTY: &str = r##"# my alacritty config
+[window]
+padding = { x = 4, y = 4 } # keep this
+
+[colors.primary]
+background = "#000000" # old background
+foreground = "#ffffff"
+
+[font]
+size = 11.0 # small screen
+"##;
+
+    #[test]
+    fn window_opacity_only_when_transparent() {
+        let style = |transparent, opacity| StyleOptions {
+            transparent,
+            opacity,
+            ..Default::default()
+        };
+        assert_eq!(style(None, Some(0.8)).window_opacity(), None);
+        assert_eq!(style(Some(false), Some(0.8)).window_opacity(), None);
+        assert_eq!(style(Some(true), Some(0.8)).window_opacity(), Some(0.8));
+        assert_eq!(style(Some(true), Some(1.5)).window_opacity(), Some(1.0));
+        assert_eq!(
+            style(Some(true), None).window_opacity(),
+            Some(DEFAULT_TRANSPARENT_OPACITY)
+        );
+    }
+
+    #[test]
+    fn colors_keep_comments_and_layout() {
+        let theme = collection::by_name("gruvbox_dark").prepare().unwrap();
+        let mut config = ALACRITTY.parse::<Config>().unwrap();
+        config.replace_colors_from_theme(theme.palette()).unwrap();
+        let text = config.to_string();
+
+        assert!(text.starts_with(
+            "# my alacritty config\n[window]\npadding = { x = 4, y = 4 } # keep this\n"
+        ));
+        let bg = theme.palette().bg[1].to_string();
+        assert!(text.contains(&format!("background = \"{bg}\" # old background\n")));
+        assert!(text.contains("[colors.normal]"));
+        assert!(text.contains("[font]\nsize = 11.0 # small screen\n"));
+    }
+
+    #[test]
+    fn font_merges_only_set_fields() {
+        let mut config = ALACRITTY.parse::<Config>().unwrap();
+        config
+            .set_font(&Font {
+                size: Some(13.5),
+                ..Default::default()
+            })
+            .unwrap();
+        config.set_font_family("JetBrainsMono Nerd Font");
+
+        let font = config.font().unwrap();
+        assert_eq!(font.size, Some(13.5));
+        assert_eq!(config.font_family(), Some("JetBrainsMono Nerd Font"));
+        let text = config.to_string();
+        assert!(text.contains("size = 13.5 # small screen\n"));
+        assert!(text.contains("padding = { x = 4, y = 4 } # keep this\n"));
+    }
+
+    #[test]
+    fn font_size_must_be_positive() {
+        assert_eq!(FontConfig::check_size(13.5).unwrap(), 13.5);
+        for size in [0.0, -3.0, f32::NAN, f32::INFINITY] {
+            assert!(FontConfig::check_size(size).is_err(), "{size}");
+        }
     }
 }
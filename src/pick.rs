@@ -0,0 +1,420 @@
+use crate::preview;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, terminal,
+};
+use std::io::Write;
+use tvibe::{
+    collection::Collection,
+    color::Color,
+    error::{Error, Result},
+    models::{PreparedTheme, StyleOptions},
+};
+
+const LIST_WIDTH: usize = 32;
+const TABS: [&str; 3] = ["All", "Dark", "Light"];
+
+struct Entry<'a> {
+    name: &'a str,
+    theme: PreparedTheme,
+}
+
+struct Picker<'a> {
+    entries: Vec<Entry<'a>>,
+    query: String,
+    tab: usize,
+    selected: usize,
+    scroll: usize,
+    // indices into `entries` matching the query and tab, best first
+    visible: Vec<usize>,
+}
+
+/// Subsequence match, higher is better. Consecutive characters and matches
+/// at the start of a word score more, `None` when `query` does not match.
+fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name = name.as_bytes();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last: Option<usize> = None;
+    for q in query.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let q = if q == b'-' {
+            b'_'
+        } else {
+            q.to_ascii_lowercase()
+        };
+        let found = (pos..name.len()).find(|&i| name[i].to_ascii_lowercase() == q)?;
+        score += 1;
+        if last.is_some_and(|l| l + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || name[found - 1] == b'_' {
+            score += 3;
+        }
+        score -= (found - pos).min(5) as i32;
+        last = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// OSC sequences that recolor the running terminal.
+fn osc_palette(theme: &PreparedTheme) -> String {
+    let p = theme.palette();
+    let rgb = |c: Color| {
+        let (r, g, b, _) = c.to_rgba();
+        format!("rgb:{r:02x}/{g:02x}/{b:02x}")
+    };
+    let mut out = String::new();
+    let (base, bright) = (p.base.colors(), p.bright.colors());
+    let indexed = base[..8]
+        .iter()
+        .chain(&bright[..8])
+        .chain([&p.base.orange, &p.base.pink]);
+    for (i, c) in indexed.enumerate() {
+        out.push_str(&format!("\x1b]4;{i};{}\x1b\\", rgb(*c)));
+    }
+    out.push_str(&format!("\x1b]10;{}\x1b\\", rgb(p.fg[1])));
+    out.push_str(&format!("\x1b]11;{}\x1b\\", rgb(p.bg[1])));
+    out.push_str(&format!("\x1b]12;{}\x1b\\", rgb(p.fg[2])));
+    out
+}
+
+const OSC_RESET: &str = "\x1b]104\x1b\\\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\";
+
+/// Raw mode and the alternate screen, left on drop whichever way the
+/// picker exits. The terminal colors are reset unless a theme was chosen.
+struct Screen {
+    reset_colors: bool,
+}
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> Result<Self> {
+        terminal::enable_raw_mode()
+            .map_err(|e| Error::Unavailable(format!("pick needs an interactive terminal: {e}")))?;
+        // from here on `drop` restores the terminal
+        let screen = Self { reset_colors: true };
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut out = std::io::stdout();
+        if self.reset_colors {
+            let _ = out.write_all(OSC_RESET.as_bytes());
+        }
+        let _ = execute!(out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl<'a> Picker<'a> {
+    fn new(entries: Vec<Entry<'a>>, tab: usize) -> Self {
+        let mut p = Self {
+            entries,
+            query: String::new(),
+            tab,
+            selected: 0,
+            scroll: 0,
+            visible: Vec::new(),
+        };
+        p.filter();
+        p
+    }
+
+    fn filter(&mut self) {
+        let mut scored = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| match self.tab {
+                1 => !e.theme.is_light(),
+                2 => e.theme.is_light(),
+                _ => true,
+            })
+            .filter_map(|(i, e)| fuzzy_score(&self.query, e.name).map(|s| (i, s)))
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        self.visible = scored.into_iter().map(|(i, _)| i).collect();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    fn current(&self) -> Option<&Entry<'a>> {
+        self.visible.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    fn move_by(&mut self, delta: isize, rows: usize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+    }
+
+    fn render(&self, cols: usize, rows: usize) -> String {
+        let mut out = String::from("\x1b[H\x1b[2J");
+        let tabs = TABS
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                if i == self.tab {
+                    format!("\x1b[7m {t} \x1b[0m")
+                } else {
+                    format!(" {t} ")
+                }
+            })
+            .collect::<String>();
+        out.push_str(&format!(
+            " tvibe pick {tabs}  \x1b[1m>\x1b[0m {}\x1b[K\r\n",
+            self.query
+        ));
+        out.push_str(&format!(
+            "\x1b[2m {} themes · type to filter · tab switch · enter apply · esc cancel\x1b[0m\r\n",
+            self.visible.len()
+        ));
+
+        let list_rows = rows.saturating_sub(3);
+        let pane = self.preview(cols.saturating_sub(LIST_WIDTH + 2));
+        for row in 0..list_rows {
+            let idx = self.scroll + row;
+            let item = match self.visible.get(idx).map(|&i| &self.entries[i]) {
+                Some(e) => {
+                    let mark = if e.theme.is_light() { "☀" } else { " " };
+                    let name = format!(" {mark} {:<w$}", e.name, w = LIST_WIDTH - 3);
+                    if idx == self.selected {
+                        format!("\x1b[7m{name}\x1b[0m")
+                    } else {
+                        name
+                    }
+                }
+                None => " ".repeat(LIST_WIDTH),
+            };
+            out.push_str(&item);
+            if let Some(line) = pane.get(row) {
+                out.push_str("  ");
+                out.push_str(line);
+            }
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    fn preview(&self, width: usize) -> Vec<String> {
+        let Some(entry) = self.current() else {
+            return vec!["no matching themes".into()];
+        };
+        let p = entry.theme.palette();
+        let swatches = |colors: &[Color]| {
+            colors
+                .iter()
+                .map(|c| format!("{}   ", c.ansi_bg()))
+                .collect::<String>()
+                + "\x1b[0m"
+        };
+        vec![
+            format!(
+                "\x1b[1m{}\x1b[0m {}",
+                entry.theme.display_name(),
+                if entry.theme.is_light() {
+                    "light"
+                } else {
+                    "dark"
+                }
+            ),
+            String::new(),
+            swatches(&p.base.colors()),
+            swatches(&p.bright.colors()),
+            swatches(&p.dim.colors()),
+            swatches(&[p.bg.as_slice(), &p.fg, &p.sel].concat()),
+            String::new(),
+        ]
+        .into_iter()
+        .chain(preview::code_sample(&entry.theme, width))
+        .collect()
+    }
+}
+
+fn run_loop<'a>(picker: &mut Picker<'a>, out: &mut impl Write) -> std::io::Result<Option<&'a str>> {
+    let mut last_applied = None;
+    loop {
+        let (cols, rows) = terminal::size()?;
+        let (cols, rows) = (cols as usize, rows as usize);
+        if let Some(e) = picker.current()
+            && last_applied != Some(e.name)
+        {
+            out.write_all(osc_palette(&e.theme).as_bytes())?;
+            last_applied = Some(e.name);
+        }
+        out.write_all(picker.render(cols, rows).as_bytes())?;
+        out.flush()?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let list_rows = rows.saturating_sub(3).max(1);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => return Ok(picker.current().map(|e| e.name)),
+            KeyCode::Up => picker.move_by(-1, list_rows),
+            KeyCode::Char('p') if ctrl => picker.move_by(-1, list_rows),
+            KeyCode::Down => picker.move_by(1, list_rows),
+            KeyCode::Char('n') if ctrl => picker.move_by(1, list_rows),
+            KeyCode::PageUp => picker.move_by(-(list_rows as isize), list_rows),
+            KeyCode::PageDown => picker.move_by(list_rows as isize, list_rows),
+            KeyCode::Tab => {
+                picker.tab = (picker.tab + 1) % TABS.len();
+                picker.filter();
+            }
+            KeyCode::BackTab => {
+                picker.tab = (picker.tab + TABS.len() - 1) % TABS.len();
+                picker.filter();
+            }
+            KeyCode::Backspace => {
+                picker.query.pop();
+                picker.filter();
+            }
+            KeyCode::Char(ch) if !ctrl => {
+                picker.query.push(ch);
+                picker.filter();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Full-screen theme picker. Recolors the terminal while browsing, returns
+/// the chosen theme name on Enter and restores the colors on Esc. Themes
+/// that fail to load are reported and left out.
+pub fn run<'a>(
+    collection: &'a Collection,
+    style: Option<&StyleOptions>,
+    dark: bool,
+    light: bool,
+) -> Result<Option<&'a str>> {
+    let mut entries = Vec::new();
+    for name in collection.names() {
+        let theme = collection.get(name).and_then(|mut theme| {
+            if let Some(s) = style {
+                theme.get_or_insert_style().merge(s);
+            }
+            theme.prepare()
+        });
+        match theme {
+            Ok(theme) => entries.push(Entry { name, theme }),
+            Err(e) => eprintln!("pick: skipped {name}: {e}"),
+        }
+    }
+    let tab = if dark {
+        1
+    } else if light {
+        2
+    } else {
+        0
+    };
+    let mut picker = Picker::new(entries, tab);
+
+    let mut out = std::io::stdout();
+    let mut screen = Screen::enter(&mut out)?;
+    let res = run_loop(&mut picker, &mut out);
+    screen.reset_colors = !matches!(res, Ok(Some(_)));
+    drop(screen);
+    Ok(res?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_matches_subsequences() {
+        assert_eq!(fuzzy_score("", "nord"), Some(0));
+        assert!(fuzzy_score("gbd", "gruvbox_dark").is_some());
+        assert_eq!(fuzzy_score("xyz", "gruvbox_dark"), None);
+        // out of order
+        assert_eq!(fuzzy_score("dg", "gruvbox_dark"), None);
+        // case, spaces and `-` for `_` are ignored
+        assert_eq!(
+            fuzzy_score("Tokyo-N", "tokyo_night"),
+            fuzzy_score("tokyo_n", "tokyo_night")
+        );
+        assert_eq!(
+            fuzzy_score("tokyo n", "tokyo_night"),
+            fuzzy_score("tokyon", "tokyo_night")
+        );
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_runs_and_word_starts() {
+        let score = |q| fuzzy_score(q, "gruvbox_dark").unwrap();
+        // consecutive characters
+        assert!(score("gruv") > score("grbx"));
+        // start of a word rather than its middle
+        assert!(score("d") > score("a"));
+        assert!(fuzzy_score("dark", "gruvbox_dark") > fuzzy_score("dark", "gruvboxdark"));
+    }
+
+    fn picker(tab: usize) -> Picker<'static> {
+        let collection = Collection::default();
+        let entries = ["dayfox", "gruvbox_dark", "gruvbox_light", "nordfox"]
+            .into_iter()
+            .map(|name| Entry {
+                name,
+                theme: collection.get(name).unwrap().prepare().unwrap(),
+            })
+            .collect();
+        Picker::new(entries, tab)
+    }
+
+    fn visible<'a>(p: &Picker<'a>) -> Vec<&'a str> {
+        p.visible.iter().map(|&i| p.entries[i].name).collect()
+    }
+
+    #[test]
+    fn filter_applies_tab_and_query() {
+        let mut p = picker(0);
+        assert_eq!(visible(&p).len(), 4);
+
+        p.tab = 1;
+        p.filter();
+        assert_eq!(visible(&p), ["gruvbox_dark", "nordfox"]);
+        p.tab = 2;
+        p.filter();
+        assert_eq!(visible(&p), ["dayfox", "gruvbox_light"]);
+
+        p.tab = 0;
+        p.query = "fox".into();
+        p.move_by(1, 10);
+        p.filter();
+        assert_eq!(visible(&p), ["dayfox", "nordfox"]);
+        assert_eq!(p.selected, 0);
+        assert_eq!(p.current().map(|e| e.name), Some("dayfox"));
+
+        p.query = "zzz".into();
+        p.filter();
+        assert!(p.current().is_none());
+    }
+
+    #[test]
+    fn filter_ranks_best_match_first() {
+        let mut p = picker(0);
+        p.query = "gl".into();
+        p.filter();
+        assert_eq!(visible(&p), ["gruvbox_light"]);
+        p.query = "nf".into();
+        p.filter();
+        assert_eq!(visible(&p).first(), Some(&"nordfox"));
+    }
+}
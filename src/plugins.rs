@@ -0,0 +1,295 @@
+//! External targets: executables named `tvibe-target-<name>` in the plugin
+//! directory or on `PATH`. tvibe writes a JSON request with the theme and
+//! its palette to the plugin's stdin and reads a JSON response from its
+//! stdout. The files listed there are written like those of the built-in
+//! targets, with backups and `--dry-run` diffs. A plugin may also write
+//! its files itself and answer `{}` or nothing. A non-zero exit or an
+//! `error` in the response fails the target.
+
+use crate::hooks;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+use tvibe::{
+    apply::TARGETS,
+    error::{Error, Result},
+    files::Writer,
+    models::{Palette, PreparedTheme, StyleOptions},
+};
+
+const PREFIX: &str = "tvibe-target-";
+
+/// Version of the request format, bumped on incompatible changes.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A `tvibe-target-*` executable, `name` is the part after the prefix.
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct Metadata<'a> {
+    name: &'a str,
+    display_name: &'a str,
+    light: bool,
+    author: Option<&'a str>,
+    url: Option<&'a str>,
+    license: Option<&'a str>,
+    tags: &'a [String],
+    family: Option<&'a str>,
+    variant: Option<&'a str>,
+}
+
+/// What a plugin reads on stdin.
+#[derive(Serialize)]
+struct Request<'a> {
+    version: u32,
+    /// The plugin must not write anything itself, returned files are only
+    /// diffed.
+    dry_run: bool,
+    theme: Metadata<'a>,
+    style: StyleOptions,
+    palette: &'a Palette,
+}
+
+/// What a plugin writes to stdout.
+#[derive(Default, Deserialize)]
+struct Response {
+    #[serde(default)]
+    files: Vec<File>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    /// Absolute path of the file.
+    path: PathBuf,
+    content: String,
+}
+
+/// The plugin directory followed by the directories of `PATH`.
+pub fn search_dirs(plugin_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![plugin_dir.to_path_buf()];
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+    dirs
+}
+
+/// Executable `tvibe-target-*` files of `dirs`, sorted by name. The first
+/// directory holding a name wins, names of built-in targets are skipped.
+pub fn discover(dirs: &[PathBuf]) -> Vec<Plugin> {
+    let mut plugins = BTreeMap::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.strip_prefix(PREFIX))
+            else {
+                continue;
+            };
+            if !name.is_empty()
+                && !TARGETS.contains(&name)
+                && !plugins.contains_key(name)
+                && is_executable(&path)
+            {
+                plugins.insert(name.to_string(), path);
+            }
+        }
+    }
+    plugins
+        .into_iter()
+        .map(|(name, path)| Plugin { name, path })
+        .collect()
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    path.is_file()
+}
+
+/// Runs `plugin` for `theme` and writes the files it returns. The plugin is
+/// killed after `timeout`, the one of the hooks.
+pub fn apply(
+    plugin: &Plugin,
+    theme: &PreparedTheme,
+    writer: &Writer,
+    timeout: Duration,
+) -> Result<()> {
+    let failed = |reason: &dyn std::fmt::Display| {
+        Error::Unavailable(format!("{PREFIX}{}: {reason}", plugin.name))
+    };
+    let request = serde_json::to_vec(&Request {
+        version: PROTOCOL_VERSION,
+        dry_run: writer.dry_run,
+        theme: Metadata {
+            name: theme.name(),
+            display_name: theme.display_name(),
+            light: theme.is_light(),
+            author: theme.author.as_deref(),
+            url: theme.url.as_deref(),
+            license: theme.license.as_deref(),
+            tags: &theme.tags,
+            family: theme.family.as_deref(),
+            variant: theme.variant.as_deref(),
+        },
+        style: theme.style(),
+        palette: theme.palette(),
+    })
+    .map_err(|e| failed(&e))?;
+
+    let (status, stdout) = hooks::execute(&mut Command::new(&plugin.path), request, timeout, true)
+        .map_err(|e| failed(&e))?;
+    if !status.success() {
+        return Err(failed(&status));
+    }
+    let response = if stdout.trim_ascii().is_empty() {
+        Response::default()
+    } else {
+        serde_json::from_slice::<Response>(&stdout)
+            .map_err(|e| failed(&format!("invalid response: {e}")))?
+    };
+    if let Some(error) = response.error {
+        return Err(failed(&error));
+    }
+    if let Some(file) = response.files.iter().find(|f| !f.path.is_absolute()) {
+        return Err(failed(&format!(
+            "invalid response: relative path '{}'",
+            file.path.display()
+        )));
+    }
+    for file in &response.files {
+        writer.write(&file.path, &file.content)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{hooks::tests::theme, test_util::TempDir};
+    use std::os::unix::fs::PermissionsExt;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// A `tvibe-target-<name>` shell script in `dir` running `body`.
+    fn script(dir: &Path, name: &str, body: &str, mode: u32) -> Plugin {
+        let path = dir.join(format!("{PREFIX}{name}"));
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        Plugin {
+            name: name.into(),
+            path,
+        }
+    }
+
+    #[test]
+    fn discover_prefers_earlier_dirs() {
+        let dir = TempDir::new("plugins-discover");
+        let (first, second) = (dir.join("first"), dir.join("second"));
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        script(&first, "foot", "", 0o755);
+        script(&first, "kitty", "", 0o644);
+        script(&first, "nvim", "", 0o755);
+        script(&second, "foot", "", 0o755);
+        script(&second, "kitty", "", 0o755);
+        script(&second, "", "", 0o755);
+
+        let plugins = discover(&[first.clone(), dir.join("missing"), second.clone()]);
+        let found = plugins
+            .iter()
+            .map(|p| (p.name.as_str(), p.path.parent().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [("foot", first.as_path()), ("kitty", second.as_path())]
+        );
+    }
+
+    #[test]
+    fn apply_writes_returned_files() {
+        let dir = TempDir::new("plugins-apply");
+        let request = dir.join("request.json");
+        let out = dir.join("colors.ini");
+        let plugin = script(
+            &dir,
+            "foot",
+            &format!(
+                r#"cat > '{}'
+echo '{{"files": [{{"path": "{}", "content": "[colors]"}}]}}'"#,
+                request.display(),
+                out.display()
+            ),
+            0o755,
+        );
+
+        let writer = Writer::new(true, false);
+        apply(&plugin, &theme(), &writer, TIMEOUT).unwrap();
+        assert!(!out.exists());
+        let request: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&request).unwrap()).unwrap();
+        assert_eq!(request["version"], PROTOCOL_VERSION);
+        assert_eq!(request["dry_run"], true);
+        assert_eq!(request["theme"]["name"], "gruvbox_dark");
+
+        apply(&plugin, &theme(), &Writer::new(false, false), TIMEOUT).unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "[colors]");
+    }
+
+    #[test]
+    fn apply_validates_the_response() {
+        let dir = TempDir::new("plugins-response");
+        let out = dir.join("written");
+        let fail = |name: &str, body: &str| {
+            let plugin = script(&dir, name, body, 0o755);
+            apply(&plugin, &theme(), &Writer::new(false, false), TIMEOUT)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let relative = format!(
+            r#"printf '{{"files": [{{"path": "{}", "content": ""}}, {{"path": "rel/x", "content": ""}}]}}'"#,
+            out.display()
+        );
+        assert!(fail("relative", &relative).contains("relative path 'rel/x'"));
+        assert!(!out.exists());
+        assert!(fail("error", r#"echo '{"error": "no config"}'"#).ends_with("no config"));
+        assert!(fail("json", "echo '{files'").contains("invalid response"));
+        assert!(fail("exit", "exit 2").contains("exit status: 2"));
+
+        let quiet = script(&dir, "quiet", "cat > /dev/null", 0o755);
+        apply(&quiet, &theme(), &Writer::new(false, false), TIMEOUT).unwrap();
+    }
+
+    #[test]
+    fn apply_kills_the_plugin_after_the_timeout() {
+        let dir = TempDir::new("plugins-timeout");
+        let slow = script(&dir, "slow", "sleep 5", 0o755);
+        let err = apply(
+            &slow,
+            &theme(),
+            &Writer::new(false, false),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+}
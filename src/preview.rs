@@ -0,0 +1,391 @@
+use tvibe::{
+    color::Color,
+    models::{Palette, PreparedTheme, StyleOptions},
+};
+
+const RESET: &str = "\x1b[0m";
+
+// «role:text» spans, everything else is plain fg1 text. Besides the syntax
+// roles of `templ::nvim` a span can use one of the editor roles handled in
+// `span_sgr` (linenr, visual, cursor, error, ...).
+const CODE_SAMPLE: [&str; 16] = [
+    "«comment:// Load a theme and count its colors»",
+    "«preproc:use» «ident:std»«bracket:::»«ident:fs»«bracket:;»",
+    "",
+    "«preproc:#[derive(Debug)]»",
+    "«keyword:pub struct» «type:Theme» «bracket:{»",
+    "    «field:name»«bracket::» «type:String»«bracket:,»",
+    "    «field:light»«bracket::» «builtin1:bool»«bracket:,»",
+    "«bracket:}»",
+    "",
+    "«keyword:fn» «func:load»«bracket:(»«variable:path»«bracket::» «operator:&»«builtin1:str»«bracket:)» «operator:->» «type:Option»«bracket:<»«type:Theme»«bracket:> {»",
+    "    «keyword:let» «variable:text» «operator:=» «ident:fs»«bracket:::»«func:read_to_string»«bracket:(»«variable:path»«bracket:).»«func:ok»«bracket:()»«operator:?»«bracket:;»",
+    "    «conditional:if» «variable:text»«bracket:.»«func:is_empty»«bracket:() {»",
+    "        «builtin0:return» «const:None»«bracket:;»",
+    "    «bracket:}»",
+    "    «builtin0:println!»«bracket:(»«string:\"{} bytes, max {}\"»«bracket:,» «variable:text»«bracket:.»«func:len»«bracket:(),» «number:0x1f» «operator:+» «number:42»«bracket:);»",
+    "    «func:parse»«bracket:(»«operator:&»«variable:text»«bracket:)» «comment:// TODO: validate»",
+];
+
+/// Background of a sample line.
+#[derive(Clone, Copy)]
+enum LineBg {
+    Normal,
+    CursorLine,
+    Delete,
+    Add,
+    Change,
+}
+
+impl LineBg {
+    fn color(self, p: &Palette) -> Color {
+        match self {
+            Self::Normal => p.bg[1],
+            Self::CursorLine => p.bg[3],
+            Self::Delete => p.diff.delete,
+            Self::Add => p.diff.add,
+            Self::Change => p.diff.change,
+        }
+    }
+}
+
+// (line background, markup), an editor window with gutter, diagnostics,
+// selection and the cursor line.
+const EDITOR_SAMPLE: [(LineBg, &str); 8] = [
+    (
+        LineBg::Normal,
+        "«linenr:   1 »«keyword:local» «variable:M» «operator:=» «bracket:{}»",
+    ),
+    (LineBg::Normal, "«linenr:   2 »"),
+    (
+        LineBg::Normal,
+        "«linenr:   3 »«keyword:function» «variable:M»«bracket:.»«func:setup»«bracket:(»«variable:opts»«bracket:)»",
+    ),
+    (
+        LineBg::Normal,
+        "«error:E»«linenr:  4 »    «keyword:local» «curl_error:cfg» «operator:=» «variable:opts» «conditional:or» «bracket:{}»  «error:■ unused local `cfg`»",
+    ),
+    (
+        LineBg::CursorLine,
+        "«cursorlinenr:   5 »    «keyword:local» «variable:name» «operator:=» «variable:opts»«bracket:.»«field:n»«cursor:a»«field:me»",
+    ),
+    (
+        LineBg::Normal,
+        "«warn:W»«linenr:  6 »    «func:print»«bracket:(»«visual:\"theme: \" .. name»«bracket:)»  «warn:■ use vim.notify»",
+    ),
+    (
+        LineBg::Normal,
+        "«info:I»«linenr:  7 »    «keyword:return» «variable:M»«bracket:.»«func:apply»«bracket:(»«variable:name»«bracket:)»  «info:■ 2 references»",
+    ),
+    (
+        LineBg::Normal,
+        "«hint:H»«linenr:  8 »«keyword:end»  «hint:■ add a return type annotation»",
+    ),
+];
+
+const DIFF_SAMPLE: [(LineBg, &str); 6] = [
+    (
+        LineBg::Normal,
+        "«info:@@ -12,5 +12,5 @@ function M.apply(name)»",
+    ),
+    (
+        LineBg::Normal,
+        "     «keyword:local» «variable:theme» «operator:=» «func:load»«bracket:(»«variable:name»«bracket:)»",
+    ),
+    (
+        LineBg::Delete,
+        "«delete:-»    «keyword:if not» «variable:theme» «keyword:then return end»",
+    ),
+    (
+        LineBg::Add,
+        "«add:+»    «keyword:if not» «variable:theme» «keyword:then» «keyword:return» «builtin0:error»«bracket:(»«string:\"no theme\"»«bracket:)» «keyword:end»",
+    ),
+    (
+        LineBg::Change,
+        "«info:~»    «func:set_colors»«bracket:(»«variable:theme»«bracket:.»«difftext:palette»«bracket:)»",
+    ),
+    (LineBg::Normal, "     «keyword:return» «variable:theme»"),
+];
+
+/// Maps a syntax role to the style category used by `StyleOptions`.
+fn style_category(role: &str) -> Option<&'static str> {
+    Some(match role {
+        "comment" => "comments",
+        "conditional" => "conditionals",
+        "const" => "constants",
+        "func" => "functions",
+        "keyword" | "statement" => "keywords",
+        "number" => "numbers",
+        "operator" => "operators",
+        "preproc" => "preprocs",
+        "string" => "strings",
+        "type" => "types",
+        "variable" => "variables",
+        _ => return None,
+    })
+}
+
+fn sgr_attrs(style: &str) -> String {
+    style
+        .split(',')
+        .filter_map(|t| match t.trim() {
+            "bold" => Some("\x1b[1m"),
+            "italic" => Some("\x1b[3m"),
+            "underline" => Some("\x1b[4m"),
+            "undercurl" => Some("\x1b[4:3m"),
+            "reverse" => Some("\x1b[7m"),
+            "strikethrough" => Some("\x1b[9m"),
+            _ => None,
+        })
+        .collect()
+}
+
+/// SGR for a span role, mirrors the highlight groups of the nvim template.
+fn span_sgr(theme: &PreparedTheme, style: &StyleOptions, role: &str) -> String {
+    let p = theme.palette();
+    // spec.diag in the nvim template
+    let diag = |kind: &str| match kind {
+        "error" => p.base.red,
+        "warn" => p.base.yellow,
+        "info" => p.base.blue,
+        _ => p.base.green,
+    };
+    match role {
+        "linenr" => p.fg[3].ansi_fg(),
+        "cursorlinenr" => format!("\x1b[1m{}", diag("warn").ansi_fg()),
+        "error" | "warn" | "info" | "hint" => diag(role).ansi_fg(),
+        "add" => p.base.green.ansi_fg(),
+        "delete" => p.base.red.ansi_fg(),
+        "curl_error" => {
+            let (r, g, b, _) = diag("error").to_rgba();
+            format!("\x1b[4:3m\x1b[58:2::{r}:{g}:{b}m")
+        }
+        "visual" if style.inverse("visual") => "\x1b[7m".into(),
+        "visual" => p.sel[0].ansi_bg(),
+        "cursor" => format!("{}{}", p.bg[1].ansi_fg(), p.fg[1].ansi_bg()),
+        "difftext" => p.diff.text.ansi_bg(),
+        _ => {
+            let attrs = style_category(role)
+                .map(|c| sgr_attrs(style.style(c)))
+                .unwrap_or_default();
+            format!("{attrs}{}", theme.syntax_color(role).ansi_fg())
+        }
+    }
+}
+
+/// Splits a `«role:text»` line into `(role, text)` spans, plain text gets
+/// an empty role.
+fn spans(line: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('«') {
+        out.push(("", &rest[..start]));
+        rest = &rest[start + '«'.len_utf8()..];
+        let end = rest.find('»').unwrap_or(rest.len());
+        out.push(rest[..end].split_once(':').unwrap_or(("", &rest[..end])));
+        rest = rest.get(end + '»'.len_utf8()..).unwrap_or("");
+    }
+    out.push(("", rest));
+    out.retain(|(_, text)| !text.is_empty());
+    out
+}
+
+/// Renders one `«role:text»` line on the `line_bg` palette color, clipped
+/// and padded to `width` columns.
+fn render_line(theme: &PreparedTheme, line_bg: LineBg, line: &str, width: usize) -> String {
+    let p = theme.palette();
+    let style = theme.style();
+    let base = format!("{}{}", line_bg.color(p).ansi_bg(), p.fg[1].ansi_fg());
+
+    let mut out = base.clone();
+    let mut len = 0;
+    for (role, text) in spans(line) {
+        let text = text.chars().take(width - len).collect::<String>();
+        len += text.chars().count();
+        if role.is_empty() {
+            out.push_str(&text);
+        } else {
+            out.push_str(&span_sgr(theme, &style, role));
+            out.push_str(&text);
+            out.push_str(RESET);
+            out.push_str(&base);
+        }
+    }
+    out.push_str(&" ".repeat(width - len));
+    out.push_str(RESET);
+    out
+}
+
+/// Small Rust snippet colored with the same role mapping as the nvim template.
+pub fn code_sample(theme: &PreparedTheme, width: usize) -> Vec<String> {
+    CODE_SAMPLE
+        .iter()
+        .map(|line| render_line(theme, LineBg::Normal, &format!(" {line}"), width))
+        .collect()
+}
+
+/// Labeled swatch rows for the terminal palette and the bg/fg/sel shades.
+fn palette_rows(theme: &PreparedTheme, width: usize) -> Vec<String> {
+    let p = theme.palette();
+    let bg1 = p.bg[1].ansi_bg();
+    let row = |name: &str, colors: &[Color]| {
+        let swatches = colors
+            .iter()
+            .map(|c| format!("{}    ", c.ansi_bg()))
+            .collect::<String>();
+        let pad = width.saturating_sub(9 + colors.len() * 4);
+        format!(
+            "{bg1}{} {name:<7} {swatches}{bg1}{}{RESET}",
+            p.fg[3].ansi_fg(),
+            " ".repeat(pad)
+        )
+    };
+    let shades = [p.bg.as_slice(), &p.fg, &p.sel].concat();
+    vec![
+        row("base", &p.base.colors()),
+        row("bright", &p.bright.colors()),
+        row("dim", &p.dim.colors()),
+        row("shades", &shades),
+    ]
+}
+
+/// Full preview: palette rows, code sample, editor window and a diff hunk.
+pub fn render(theme: &PreparedTheme, width: usize) -> Vec<String> {
+    let blank = || render_line(theme, LineBg::Normal, "", width);
+    let mut lines = vec![blank()];
+    lines.extend(palette_rows(theme, width));
+    lines.push(blank());
+    lines.extend(code_sample(theme, width));
+    lines.push(blank());
+    lines.extend(
+        EDITOR_SAMPLE
+            .iter()
+            .map(|(line_bg, line)| render_line(theme, *line_bg, line, width)),
+    );
+    lines.push(blank());
+    lines.extend(
+        DIFF_SAMPLE
+            .iter()
+            .map(|(line_bg, line)| render_line(theme, *line_bg, &format!(" {line}"), width)),
+    );
+    lines.push(blank());
+    lines
+}
+
+pub fn print(theme: &PreparedTheme) {
+    let width = crossterm::terminal::size()
+        .map(|(cols, _)| cols as usize)
+        .unwrap_or(80)
+        .clamp(40, 100);
+    for line in render(theme, width) {
+        println!("{line}");
+    }
+}
+
+/// The code sample as `(text, color)` spans, for renderers other than the
+/// terminal.
+pub fn code_sample_spans(theme: &PreparedTheme) -> Vec<Vec<(&'static str, Color)>> {
+    CODE_SAMPLE
+        .iter()
+        .map(|line| {
+            spans(line)
+                .into_iter()
+                .map(|(role, text)| (text, theme.syntax_color(role)))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(style: &str) -> PreparedTheme {
+        let mut theme = tvibe::collection::by_name("nordfox");
+        theme.style = Some(toml::from_str(style).unwrap());
+        theme.prepare().unwrap()
+    }
+
+    /// The text of a rendered line without its SGR sequences.
+    fn visible(line: &str) -> String {
+        let mut out = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn spans_split_roles_from_plain_text() {
+        assert_eq!(
+            spans("let «keyword:fn» x«bracket:;»"),
+            [
+                ("", "let "),
+                ("keyword", "fn"),
+                ("", " x"),
+                ("bracket", ";")
+            ]
+        );
+        assert_eq!(
+            spans("«:plain»«open:rest"),
+            [("", "plain"), ("open", "rest")]
+        );
+        assert!(spans("").is_empty());
+    }
+
+    #[test]
+    fn lines_are_clipped_and_padded_to_the_width() {
+        let theme = theme("");
+        let short = render_line(&theme, LineBg::Normal, "«keyword:fn» main", 12);
+        assert_eq!(visible(&short), "fn main     ");
+        let long = render_line(&theme, LineBg::Normal, "«string:\"a long string\"»", 6);
+        assert_eq!(visible(&long), "\"a lon");
+
+        let p = theme.palette();
+        assert!(short.starts_with(&format!("{}{}", p.bg[1].ansi_bg(), p.fg[1].ansi_fg())));
+        let cursor_line = render_line(&theme, LineBg::CursorLine, "", 4);
+        assert!(cursor_line.starts_with(&p.bg[3].ansi_bg()));
+    }
+
+    #[test]
+    fn roles_use_syntax_colors_and_styles() {
+        let theme = theme(
+            r#"[styles]
+comments = "italic"
+[inverse]
+visual = true"#,
+        );
+        let style = theme.style();
+        let keyword = theme.syntax_color("keyword").ansi_fg();
+        assert_eq!(span_sgr(&theme, &style, "keyword"), keyword);
+        let comment = theme.syntax_color("comment").ansi_fg();
+        assert_eq!(
+            span_sgr(&theme, &style, "comment"),
+            format!("\x1b[3m{comment}")
+        );
+        assert_eq!(span_sgr(&theme, &style, "visual"), "\x1b[7m");
+        let p = theme.palette();
+        assert_eq!(span_sgr(&theme, &style, "error"), p.base.red.ansi_fg());
+        assert_eq!(
+            span_sgr(&theme, &StyleOptions::default(), "visual"),
+            p.sel[0].ansi_bg()
+        );
+    }
+
+    #[test]
+    fn render_lays_out_every_section_at_full_width() {
+        let lines = render(&theme(""), 60);
+        // blank lines around the palette rows, code, editor and diff samples
+        let expected = 5 + 4 + CODE_SAMPLE.len() + EDITOR_SAMPLE.len() + DIFF_SAMPLE.len();
+        assert_eq!(lines.len(), expected);
+        for line in &lines {
+            assert_eq!(visible(line).chars().count(), 60, "{line:?}");
+        }
+        assert!(visible(&lines[1]).starts_with(" base    "));
+    }
+}
@@ -0,0 +1,342 @@
+use crate::fonts::FontFace;
+use ab_glyph::{Font, FontRef, GlyphId, InvalidFont, PxScale, ScaleFont, point};
+use std::io::IsTerminal;
+use tvibe::error::{Error, Result};
+
+const FG: [u8; 3] = [0xd8, 0xd8, 0xd8];
+const BG: [u8; 3] = [0x1c, 0x1c, 0x1c];
+const PIXEL_SIZE: f32 = 28.0;
+const MIN_BLOCK_SIZE: f32 = 10.0;
+const MAX_BLOCK_SIZE: f32 = 16.0;
+const SIXEL_LEVELS: usize = 16;
+
+/// Ligatures, box drawing, powerline and devicon glyphs.
+const SAMPLE: [&str; 6] = [
+    "fn main() -> Result<(), Error> { x != y && a >= b }",
+    "=> == === != !== <= >= -> <- :: |> </> /* */ www",
+    "┌──┬──┐ ╭──╮ ▁▂▃▄▅▆▇█ ░▒▓ ●○◆",
+    "\u{e0b6}\u{e0b4} \u{e0b0}\u{e0b1} \u{e0b2}\u{e0b3} \u{e0a0} main \u{e0a2} 12:34",
+    "\u{e7a8} rust  \u{e620} lua  \u{e615} conf  \u{f09b} git  \u{f489} term  \u{f07b} dir",
+    "abcdefghijklm ABCDEFGHIJKLM 0123456789 O0 Il1",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+    Blocks,
+}
+
+impl Protocol {
+    /// Guesses the graphics protocol from the environment, half blocks work
+    /// everywhere.
+    pub fn detect() -> Self {
+        if !std::io::stdout().is_terminal() {
+            return Self::Blocks;
+        }
+        let env = |k: &str| std::env::var(k).unwrap_or_default();
+        let (term, program) = (env("TERM"), env("TERM_PROGRAM"));
+        if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || matches!(program.as_str(), "ghostty" | "WezTerm")
+        {
+            Self::Kitty
+        } else if ["foot", "mlterm", "yaft", "contour"]
+            .iter()
+            .any(|t| term.contains(t))
+            || program == "iTerm.app"
+        {
+            Self::Sixel
+        } else {
+            Self::Blocks
+        }
+    }
+}
+
+/// Grayscale coverage bitmap.
+struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<f32>,
+}
+
+impl Bitmap {
+    fn rgb(&self, i: usize) -> [u8; 3] {
+        let c = self.pixels[i].clamp(0.0, 1.0);
+        std::array::from_fn(|k| (BG[k] as f32 + (FG[k] as f32 - BG[k] as f32) * c).round() as u8)
+    }
+}
+
+struct Shaped {
+    // (glyph id, x, y offset) in font units
+    glyphs: Vec<(u16, i32, i32)>,
+    advance: i32,
+}
+
+fn shape(face: &rustybuzz::Face, line: &str) -> Shaped {
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(line);
+    let out = rustybuzz::shape(face, &[], buffer);
+    let mut x = 0;
+    let mut glyphs = Vec::new();
+    for (info, pos) in out.glyph_infos().iter().zip(out.glyph_positions()) {
+        glyphs.push((info.glyph_id as u16, x + pos.x_offset, pos.y_offset));
+        x += pos.x_advance;
+    }
+    Shaped { glyphs, advance: x }
+}
+
+/// Shapes `lines` with rustybuzz (so ligatures form) and rasterizes them
+/// with ab_glyph at `px` pixels per line.
+fn rasterize(data: &[u8], index: u32, lines: &[Shaped], px: f32) -> Result<Bitmap, InvalidFont> {
+    let font = FontRef::try_from_slice_and_index(data, index)?;
+    let scaled = font.as_scaled(PxScale::from(px));
+    let units = scaled.h_scale_factor();
+    let line_height = (scaled.height() + scaled.line_gap()).ceil();
+
+    let pad = (px / 2.0).ceil() as usize;
+    let width = lines.iter().map(|l| l.advance).max().unwrap_or(0) as f32 * units;
+    let width = width.ceil() as usize + pad * 2;
+    let height = (line_height * lines.len() as f32) as usize + pad * 2;
+    let mut bitmap = Bitmap {
+        width,
+        height,
+        pixels: vec![0.0; width * height],
+    };
+
+    for (row, line) in lines.iter().enumerate() {
+        let baseline = pad as f32 + row as f32 * line_height + scaled.ascent();
+        for &(id, x, y) in &line.glyphs {
+            let glyph = GlyphId(id).with_scale_and_position(
+                px,
+                point(pad as f32 + x as f32 * units, baseline - y as f32 * units),
+            );
+            let Some(outline) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            outline.draw(|gx, gy, c| {
+                let x = bounds.min.x as i64 + gx as i64;
+                let y = bounds.min.y as i64 + gy as i64;
+                if (0..width as i64).contains(&x) && (0..height as i64).contains(&y) {
+                    let p = &mut bitmap.pixels[y as usize * width + x as usize];
+                    *p = (*p + c).min(1.0);
+                }
+            });
+        }
+    }
+    Ok(bitmap)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - i * 8));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn kitty(bitmap: &Bitmap) -> String {
+    let rgb = (0..bitmap.pixels.len())
+        .flat_map(|i| bitmap.rgb(i))
+        .collect::<Vec<_>>();
+    let data = base64(&rgb);
+    let chunks = data.as_bytes().chunks(4096).collect::<Vec<_>>();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=24,s={},v={},m={more};{chunk}\x1b\\",
+                bitmap.width, bitmap.height
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+fn sixel(bitmap: &Bitmap) -> String {
+    let (w, h) = (bitmap.width, bitmap.height);
+    let level = |x: usize, y: usize| {
+        let c = bitmap.pixels[y * w + x].clamp(0.0, 1.0);
+        (c * (SIXEL_LEVELS - 1) as f32).round() as usize
+    };
+    let mut out = format!("\x1bPq\"1;1;{w};{h}");
+    for i in 0..SIXEL_LEVELS {
+        let c = i as f32 / (SIXEL_LEVELS - 1) as f32;
+        let pct = |k: usize| ((BG[k] as f32 + (FG[k] as f32 - BG[k] as f32) * c) / 2.55).round();
+        out.push_str(&format!("#{i};2;{};{};{}", pct(0), pct(1), pct(2)));
+    }
+    for band in (0..h).step_by(6) {
+        for color in 0..SIXEL_LEVELS {
+            let row = (0..w)
+                .map(|x| {
+                    let bits = (0..6)
+                        .filter(|k| band + k < h && level(x, band + k) == color)
+                        .fold(0u8, |b, k| b | 1 << k);
+                    (63 + bits) as char
+                })
+                .collect::<Vec<_>>();
+            if row.iter().all(|&c| c == '?') {
+                continue;
+            }
+            out.push_str(&format!("#{color}"));
+            // run-length encode repeated sixels
+            let mut i = 0;
+            while i < row.len() {
+                let run = row[i..].iter().take_while(|&&c| c == row[i]).count();
+                if run > 3 {
+                    out.push_str(&format!("!{run}{}", row[i]));
+                } else {
+                    out.extend(std::iter::repeat_n(row[i], run));
+                }
+                i += run;
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\\n");
+    out
+}
+
+/// Two pixels per cell with `▀`, foreground on top and background below.
+fn blocks(bitmap: &Bitmap) -> String {
+    let mut out = String::new();
+    for y in (0..bitmap.height).step_by(2) {
+        for x in 0..bitmap.width {
+            let [r, g, b] = bitmap.rgb(y * bitmap.width + x);
+            let [br, bg, bb] = if y + 1 < bitmap.height {
+                bitmap.rgb((y + 1) * bitmap.width + x)
+            } else {
+                BG
+            };
+            out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀"));
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Renders the sample text in `face` for the terminal.
+pub fn preview(face: &FontFace, protocol: Protocol) -> Result<String> {
+    let data = std::fs::read(&face.path).map_err(|e| Error::io(&face.path, e))?;
+    let unsupported =
+        || Error::Unavailable(format!("{}: unsupported font file", face.path.display()));
+    let hb = rustybuzz::Face::from_slice(&data, face.index).ok_or_else(unsupported)?;
+    let mut lines = SAMPLE.iter().map(|l| shape(&hb, l)).collect::<Vec<_>>();
+
+    let px = match protocol {
+        Protocol::Blocks => {
+            // one pixel per column, shrink until the widest line fits and
+            // wrap the sample when even the smallest size is too wide
+            let cols = crossterm::terminal::size().map_or(80.0, |(c, _)| c as f32) - 2.0;
+            let units = FontRef::try_from_slice_and_index(&data, face.index)
+                .map_err(|_| unsupported())?
+                .as_scaled(PxScale::from(1.0))
+                .h_scale_factor();
+            let widest = lines.iter().map(|l| l.advance).max().unwrap_or(1).max(1) as f32;
+            // the bitmap adds half a line height of padding on each side
+            let px = cols / (widest * units + 1.0);
+            if px < MIN_BLOCK_SIZE {
+                let chars = SAMPLE.iter().map(|l| l.chars().count()).max().unwrap_or(1);
+                let char_width = widest * units / chars as f32 * MIN_BLOCK_SIZE;
+                let per_line = ((cols - MIN_BLOCK_SIZE) / char_width) as usize;
+                lines = SAMPLE
+                    .iter()
+                    .flat_map(|l| {
+                        let chars = l.chars().collect::<Vec<_>>();
+                        chars
+                            .chunks(per_line.max(1))
+                            .map(|c| c.iter().collect::<String>())
+                            .collect::<Vec<_>>()
+                    })
+                    .map(|l| shape(&hb, &l))
+                    .collect();
+            }
+            px.clamp(MIN_BLOCK_SIZE, MAX_BLOCK_SIZE)
+        }
+        _ => PIXEL_SIZE,
+    };
+    let bitmap = rasterize(&data, face.index, &lines, px).map_err(|_| unsupported())?;
+    Ok(match protocol {
+        Protocol::Kitty => kitty(&bitmap),
+        Protocol::Sixel => sixel(&bitmap),
+        Protocol::Blocks => blocks(&bitmap),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(width: usize, pixels: Vec<f32>) -> Bitmap {
+        Bitmap {
+            width,
+            height: pixels.len() / width,
+            pixels,
+        }
+    }
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0xfd, 0x00]), "//79AA==");
+    }
+
+    #[test]
+    fn kitty_splits_the_payload_into_4096_byte_chunks() {
+        let small = kitty(&bitmap(2, vec![0.0; 4]));
+        assert!(small.starts_with("\x1b_Ga=T,f=24,s=2,v=2,m=0;"));
+        assert_eq!(small.matches("\x1b_G").count(), 1);
+
+        // 40x40 RGB is 4800 bytes, 6400 in base64
+        let big = bitmap(40, vec![1.0; 1600]);
+        let out = kitty(&big);
+        let chunks = out
+            .trim_end()
+            .split("\x1b\\")
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 2);
+        let (head, first) = chunks[0].split_once(';').unwrap();
+        assert_eq!(head, "\x1b_Ga=T,f=24,s=40,v=40,m=1");
+        let (head, second) = chunks[1].split_once(';').unwrap();
+        assert_eq!(head, "\x1b_Gm=0");
+        assert_eq!((first.len(), second.len()), (4096, 6400 - 4096));
+        let rgb = (0..1600).flat_map(|i| big.rgb(i)).collect::<Vec<_>>();
+        assert_eq!(format!("{first}{second}"), base64(&rgb));
+    }
+
+    #[test]
+    fn sixel_run_length_encodes_long_runs() {
+        // one row: five full pixels, then two empty ones
+        let out = sixel(&bitmap(7, vec![1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0]));
+        assert!(out.starts_with("\x1bPq\"1;1;7;1#0;2;11;11;11"));
+        let data = out.rsplit_once(";85;85;85").unwrap().1;
+        assert_eq!(data, "#0!5?@@$#15!5@??$-\x1b\\\n");
+
+        let short = sixel(&bitmap(3, vec![0.0; 3]));
+        assert!(short.ends_with("#0@@@$-\x1b\\\n"));
+    }
+}
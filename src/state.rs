@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tvibe::{
+    error::{Error, Result},
+    files,
+};
+
+const MAX_HISTORY_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub name: String,
+    /// Unix seconds
+    pub timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub font: Option<String>,
+    #[serde(default)]
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<FileHash>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileHash {
+    pub path: PathBuf,
+    pub hash: String,
+}
+
+impl FileHash {
+    pub fn of(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            path: path.into(),
+            hash: hash_file(path)?,
+        })
+    }
+
+    /// Whether the file still has the content tvibe wrote.
+    pub fn is_intact(&self) -> bool {
+        hash_file(&self.path).is_ok_and(|h| h == self.hash)
+    }
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        let buff = std::fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        toml::from_str::<History>(&buff).map_err(|e| Error::config(e).in_file(path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+        let content = toml::to_string_pretty(self).map_err(|e| Error::config(e).in_file(path))?;
+        files::atomic_write(path, &content).map_err(|e| Error::io(path, e))
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let extra = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..extra);
+        }
+    }
+
+    pub fn current(&self) -> Option<&HistoryEntry> {
+        self.entries.last()
+    }
+
+    /// Entry `n` steps back from the current one, `0` is the current entry.
+    pub fn back(&self, n: usize) -> Option<&HistoryEntry> {
+        self.entries
+            .len()
+            .checked_sub(n + 1)
+            .map(|i| &self.entries[i])
+    }
+
+    /// Drops the last `steps` entries, making [`History::back`]`(steps)` the
+    /// current one.
+    pub fn rewind(&mut self, steps: usize) {
+        self.entries
+            .truncate(self.entries.len().saturating_sub(steps));
+    }
+}
+
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    Ok(format!("{:016x}", fnv1a(&std::fs::read(path)?)))
+}
+
+// FNV-1a 64, enough to notice a file was edited after tvibe wrote it.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |h, b| {
+        (h ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn entry(name: &str) -> HistoryEntry {
+        HistoryEntry {
+            name: name.into(),
+            timestamp: 0,
+            font: None,
+            targets: vec!["nvim".into()],
+            files: Vec::new(),
+        }
+    }
+
+    fn names(history: &History) -> Vec<&str> {
+        history.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn push_keeps_the_last_entries() {
+        let mut history = History::default();
+        for i in 0..MAX_HISTORY_ENTRIES + 3 {
+            history.push(entry(&i.to_string()));
+        }
+        assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.entries[0].name, "3");
+        assert_eq!(
+            history.current().unwrap().name,
+            (MAX_HISTORY_ENTRIES + 2).to_string()
+        );
+    }
+
+    #[test]
+    fn undo_goes_back_and_rewinds() {
+        let mut history = History::default();
+        for name in ["nord", "dayfox", "vesper"] {
+            history.push(entry(name));
+        }
+        assert_eq!(history.back(0).unwrap().name, "vesper");
+        assert_eq!(history.back(2).unwrap().name, "nord");
+        assert!(history.back(3).is_none());
+
+        history.rewind(1);
+        assert_eq!(names(&history), ["nord", "dayfox"]);
+        history.rewind(5);
+        assert!(history.current().is_none());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = TempDir::new("history");
+        let path = dir.join("tvibe/history.toml");
+        assert!(History::load(&path).unwrap().entries.is_empty());
+
+        let mut history = History::default();
+        history.push(entry("nord"));
+        history.push(HistoryEntry {
+            font: Some("JetBrainsMono Nerd Font".into()),
+            ..entry("dayfox")
+        });
+        history.save(&path).unwrap();
+        assert!(!dir.join("tvibe/.history.toml.tvibe.tmp").exists());
+
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(names(&loaded), ["nord", "dayfox"]);
+        assert_eq!(
+            loaded.entries[1].font.as_deref(),
+            Some("JetBrainsMono Nerd Font")
+        );
+        assert_eq!(loaded.entries[1].targets, ["nvim"]);
+    }
+
+    #[test]
+    fn edited_files_are_not_intact() {
+        let dir = TempDir::new("hash");
+        let path = dir.join("init.lua");
+        std::fs::write(&path, "tvibe\n").unwrap();
+        let hash = FileHash::of(&path).unwrap();
+        assert!(hash.is_intact());
+
+        std::fs::write(&path, "edited\n").unwrap();
+        assert!(!hash.is_intact());
+        std::fs::remove_file(&path).unwrap();
+        assert!(!hash.is_intact());
+    }
+
+    #[test]
+    fn fnv1a_matches_reference() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
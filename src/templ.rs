@@ -1,8 +1,55 @@
-use crate::models;
+use crate::models::{self, SyntaxColors};
 
-pub fn nvim(theme: &mut models::Theme) -> String {
+const SYNTAX_COMMENTS: [(&str, &str); 21] = [
+    ("bracket", "Brackets and Punctuation"),
+    ("builtin0", "Builtin variable"),
+    ("builtin1", "Builtin type"),
+    ("builtin2", "Builtin const"),
+    ("builtin3", "Not used"),
+    ("comment", "Comment"),
+    ("conditional", "Conditional and loop"),
+    ("const", "Constants, imports and booleans"),
+    ("dep", "Deprecated"),
+    ("field", "Field"),
+    ("func", "Functions and Titles"),
+    ("ident", "Identifiers"),
+    ("keyword", "Keywords"),
+    ("number", "Numbers"),
+    ("operator", "Operators"),
+    ("preproc", "PreProc"),
+    ("regex", "Regex"),
+    ("statement", "Statements"),
+    ("string", "Strings"),
+    ("type", "Types"),
+    ("variable", "Variables"),
+];
+
+fn syntax_table(overrides: Option<&SyntaxColors>, is_light: bool) -> String {
+    SYNTAX_COMMENTS
+        .iter()
+        .map(|(role, comment)| {
+            let value = overrides
+                .and_then(|s| s.get(role))
+                .unwrap_or_else(|| SyntaxColors::default_ref(role, is_light));
+            let expr = if value.starts_with('#') {
+                format!("\"{value}\"")
+            } else {
+                format!("P.{value}")
+            };
+            format!(
+                "        {role:<11} = {:<19} -- {comment}",
+                format!("{expr},")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn nvim(theme: &models::PreparedTheme) -> String {
     let is_light = theme.is_light();
-    let c = theme.get_or_insert_colors();
+    let syn = syntax_table(theme.syntax.as_ref(), is_light);
+    let style = theme.style();
+    let p = theme.palette();
     const HEAD: &str = r###"
 local function themeSyncExe()
     vim.cmd("highlight clear")
@@ -23,6 +70,7 @@ local function themeSyncExe()
         pink    = {{ base = "{pink_base}",    bright = "{pink_bright}",    dim = "{pink_dim}" }},
         comment = "{comment}",
         status_line = "{status_line}",
+        variable = "{variable}",
         bg0     = "{bg0}", -- Dark bg (status line and float)
         bg1     = "{bg1}", -- Default bg
         bg2     = "{bg2}", -- Lighter bg (colorcolm folds)
@@ -42,71 +90,54 @@ local function themeSyncExe()
         }}
     }}
 "###,
-        black_base = c.base.black,
-        black_bright = c.bright.as_ref().unwrap().black,
-        black_dim = c.dim.as_ref().unwrap().black,
-        red_base = c.base.red,
-        red_bright = c.bright.as_ref().unwrap().red,
-        red_dim = c.dim.as_ref().unwrap().red,
-        green_base = c.base.green,
-        green_bright = c.bright.as_ref().unwrap().green,
-        green_dim = c.dim.as_ref().unwrap().green,
-        yellow_base = c.base.yellow,
-        yellow_bright = c.bright.as_ref().unwrap().yellow,
-        yellow_dim = c.dim.as_ref().unwrap().yellow,
-        blue_base = c.base.blue,
-        blue_bright = c.bright.as_ref().unwrap().blue,
-        blue_dim = c.dim.as_ref().unwrap().blue,
-        magenta_base = c.base.magenta,
-        magenta_bright = c.bright.as_ref().unwrap().magenta,
-        magenta_dim = c.dim.as_ref().unwrap().magenta,
-        cyan_base = c.base.cyan,
-        cyan_bright = c.bright.as_ref().unwrap().cyan,
-        cyan_dim = c.dim.as_ref().unwrap().cyan,
-        white_base = c.base.white,
-        white_bright = c.bright.as_ref().unwrap().white,
-        white_dim = c.dim.as_ref().unwrap().white,
-        orange_base = c.base.orange.as_ref().unwrap_or(&c.base.yellow),
-        orange_bright = c
-            .bright
-            .as_ref()
-            .unwrap()
-            .orange
-            .as_ref()
-            .unwrap_or(&c.base.yellow),
-        orange_dim = c
-            .dim
-            .as_ref()
-            .unwrap()
-            .orange
-            .as_ref()
-            .unwrap_or(&c.base.yellow),
-        pink_base = c.base.pink.as_ref().unwrap_or(&c.base.red),
-        pink_bright = c
-            .bright
-            .as_ref()
-            .unwrap()
-            .pink
-            .as_ref()
-            .unwrap_or(&c.base.red),
-        pink_dim = c.dim.as_ref().unwrap().pink.as_ref().unwrap_or(&c.base.red),
-        comment = c.comment.as_ref().unwrap(),
-        status_line = c.status_line.as_ref().unwrap(),
-        bg0 = c.background.index(0),
-        bg1 = c.background.index(1),
-        bg2 = c.background.index(2),
-        bg3 = c.background.index(3),
-        bg4 = c.background.index(4),
-        fg0 = c.foreground.index(0),
-        fg1 = c.foreground.index(1),
-        fg2 = c.foreground.index(2),
-        fg3 = c.foreground.index(3),
-        sel0 = c.selection.index(0),
-        sel1 = c.selection.index(1),
-        diff_add = c.diff.as_ref().unwrap().add.as_ref().unwrap(),
-        diff_delete = c.diff.as_ref().unwrap().delete.as_ref().unwrap(),
-        diff_change = c.diff.as_ref().unwrap().change.as_ref().unwrap(),
-        diff_text = c.diff.as_ref().unwrap().text.as_ref().unwrap(),
+        black_base = p.base.black,
+        black_bright = p.bright.black,
+        black_dim = p.dim.black,
+        red_base = p.base.red,
+        red_bright = p.bright.red,
+        red_dim = p.dim.red,
+        green_base = p.base.green,
+        green_bright = p.bright.green,
+        green_dim = p.dim.green,
+        yellow_base = p.base.yellow,
+        yellow_bright = p.bright.yellow,
+        yellow_dim = p.dim.yellow,
+        blue_base = p.base.blue,
+        blue_bright = p.bright.blue,
+        blue_dim = p.dim.blue,
+        magenta_base = p.base.magenta,
+        magenta_bright = p.bright.magenta,
+        magenta_dim = p.dim.magenta,
+        cyan_base = p.base.cyan,
+        cyan_bright = p.bright.cyan,
+        cyan_dim = p.dim.cyan,
+        white_base = p.base.white,
+        white_bright = p.bright.white,
+        white_dim = p.dim.white,
+        orange_base = p.base.orange,
+        orange_bright = p.bright.orange,
+        orange_dim = p.dim.orange,
+        pink_base = p.base.pink,
+        pink_bright = p.bright.pink,
+        pink_dim = p.dim.pink,
+        comment = p.comment,
+        status_line = p.status_line,
+        variable = p.variable,
+        bg0 = p.bg[0],
+        bg1 = p.bg[1],
+        bg2 = p.bg[2],
+        bg3 = p.bg[3],
+        bg4 = p.bg[4],
+        fg0 = p.fg[0],
+        fg1 = p.fg[1],
+        fg2 = p.fg[2],
+        fg3 = p.fg[3],
+        sel0 = p.sel[0],
+        sel1 = p.sel[1],
+        diff_add = p.diff.add,
+        diff_delete = p.diff.delete,
+        diff_change = p.diff.change,
+        diff_text = p.diff.text,
     );
 
     let spec = format!(
@@ -127,52 +158,29 @@ local function themeSyncExe()
         ignored  = P.comment,
     }}
     local syn = {{
-        bracket     = P.fg2,           -- Brackets and Punctuation
-        builtin0    = P.red.base,      -- Builtin variable
-        builtin1    = P.cyan.{shade},    -- Builtin type
-        builtin2    = P.orange.{shade},  -- Builtin const
-        builtin3    = P.red.{shade},     -- Not used
-        comment     = P.comment,       -- Comment
-        conditional = P.magenta.{shade}, -- Conditional and loop
-        const       = P.orange.{shade},  -- Constants, imports and booleans
-        dep         = P.fg3,           -- Deprecated
-        field       = P.blue.base,     -- Field
-        func        = P.blue.{shade},    -- Functions and Titles
-        ident       = P.cyan.base,     -- Identifiers
-        keyword     = P.magenta.base,  -- Keywords
-        number      = P.orange.base,   -- Numbers
-        operator    = P.fg2,           -- Operators
-        preproc     = P.pink.{shade},    -- PreProc
-        regex       = P.yellow.{shade},  -- Regex
-        statement   = P.magenta.base,  -- Statements
-        string      = P.green.base,    -- Strings
-        type        = P.yellow.base,   -- Types
-        variable    = "{variable_color}",    -- Variables
+{syn}
     }}
-    local trans = false
-    local inactive = false
+    local trans = {trans}
+    local inactive = {inactive}
     local inv = {{
-        match_paren = false,
-        visual = false,
-        search = false,
+        match_paren = {inv_match_paren},
+        visual = {inv_visual},
+        search = {inv_search},
     }}
     local stl = {{
-        comments = "NONE",
-        conditionals = "NONE",
-        constants = "NONE",
-        functions = "NONE",
-        keywords = "NONE",
-        numbers = "NONE",
-        operators = "NONE",
-        preprocs = "NONE",
-        strings = "NONE",
-        types = "NONE",
-        variables = "NONE",
+{stl}
     }}
 "###,
-        // variable_color = if theme.is_light() {"black"} else {"white"},
-        variable_color = c.variable.as_ref().unwrap(),
-        shade = if is_light { "dim" } else { "bright" },
+        trans = style.is_transparent(),
+        inactive = style.is_dim_inactive(),
+        inv_match_paren = style.inverse("match_paren"),
+        inv_visual = style.inverse("visual"),
+        inv_search = style.inverse("search"),
+        stl = models::STYLE_CATEGORIES
+            .iter()
+            .map(|c| format!("        {c} = \"{}\",", style.style(c)))
+            .collect::<Vec<_>>()
+            .join("\n"),
     );
     const TAIL: &str = r###"
     for group, opts in pairs({
@@ -403,3 +411,109 @@ themeSyncExe()
 
     format!("{HEAD}{palette}{spec}{TAIL}")
 }
+
+pub fn lualine(theme: &models::PreparedTheme) -> String {
+    let p = theme.palette();
+    format!(
+        r###"-- Generated by tvibe, changes will be overwritten.
+-- Usage: require("lualine").setup({{ options = {{ theme = "tvibe" }} }})
+local P = {{
+    blue        = "{blue}",
+    green       = "{green}",
+    magenta     = "{magenta}",
+    red         = "{red}",
+    yellow      = "{yellow}",
+    bg0         = "{bg0}",
+    bg2         = "{bg2}",
+    status_line = "{status_line}",
+    fg1         = "{fg1}",
+    fg2         = "{fg2}",
+    fg3         = "{fg3}",
+}}
+
+local function mode(accent)
+    return {{
+        a = {{ fg = P.bg0, bg = accent, gui = "bold" }},
+        b = {{ fg = P.fg1, bg = P.bg2 }},
+        c = {{ fg = P.fg2, bg = P.status_line }},
+    }}
+end
+
+return {{
+    normal = mode(P.blue),
+    insert = mode(P.green),
+    visual = mode(P.magenta),
+    replace = mode(P.red),
+    command = mode(P.yellow),
+    inactive = {{
+        a = {{ fg = P.fg3, bg = P.bg0, gui = "bold" }},
+        b = {{ fg = P.fg3, bg = P.bg0 }},
+        c = {{ fg = P.fg3, bg = P.bg0 }},
+    }},
+}}
+"###,
+        blue = p.base.blue,
+        green = p.base.green,
+        magenta = p.base.magenta,
+        red = p.base.red,
+        yellow = p.base.yellow,
+        bg0 = p.bg[0],
+        bg2 = p.bg[2],
+        status_line = p.status_line,
+        fg1 = p.fg[1],
+        fg2 = p.fg[2],
+        fg3 = p.fg[3],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection;
+
+    #[test]
+    fn nvim_uses_syntax_overrides_and_shade_defaults() {
+        let mut theme = collection::by_name("nordfox");
+        theme.syntax = Some(
+            toml::from_str(
+                r##"keyword = "pink.bright"
+string = "#123456""##,
+            )
+            .unwrap(),
+        );
+        let out = nvim(&theme.prepare().unwrap());
+        assert!(out.contains("        keyword     = P.pink.bright,      -- Keywords"));
+        assert!(out.contains(r##"        string      = "#123456",          -- Strings"##));
+        // defaults pick the bright shades on dark themes, dim ones on light
+        assert!(out.contains("        func        = P.blue.bright,"));
+        let light = nvim(&collection::by_name("dayfox").prepare().unwrap());
+        assert!(light.contains("        func        = P.blue.dim,"));
+        assert!(light.contains("        keyword     = P.magenta.base,"));
+    }
+
+    #[test]
+    fn nvim_writes_the_style_table() {
+        let mut theme = collection::by_name("nordfox");
+        theme.style = Some(
+            toml::from_str(
+                r##"transparent = true
+[inverse]
+visual = true
+[styles]
+comments = "italic"
+keywords = "bold,italic""##,
+            )
+            .unwrap(),
+        );
+        let out = nvim(&theme.prepare().unwrap());
+        assert!(out.contains("    local trans = true\n    local inactive = false\n"));
+        assert!(out.contains("        match_paren = false,\n        visual = true,\n"));
+        assert!(out.contains("        comments = \"italic\",\n"));
+        assert!(out.contains("        keywords = \"bold,italic\",\n"));
+        assert!(out.contains("        types = \"NONE\",\n"));
+
+        let plain = nvim(&collection::by_name("nordfox").prepare().unwrap());
+        assert!(plain.contains("    local trans = false\n"));
+        assert!(plain.contains("        comments = \"NONE\",\n"));
+    }
+}